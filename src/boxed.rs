@@ -0,0 +1,59 @@
+use crate::{InitHandle, RingArena};
+use std::ops::{Deref, DerefMut};
+
+/// Handle to a single `T` in a [`RingArena<T>`], see [`RingArena::alloc_one`].
+///
+/// Like any other handle, it keeps its chunk in use until it's dropped.
+pub struct ArenaBox<T>(InitHandle<T>);
+
+impl<T> RingArena<T> {
+    /// Moves `value` into the arena.
+    ///
+    /// # Panics
+    /// Panics if [`RingArena::try_allocate`] fails.
+    pub fn alloc_one(&mut self, value: T) -> ArenaBox<T> {
+        let mut handle = self.allocate(1);
+        handle.as_mut_slice()[0].write(value);
+
+        // SAFETY: the only element has just been initialized
+        ArenaBox(unsafe { handle.assume_init() })
+    }
+}
+
+impl<T> ArenaBox<T> {
+    /// Moves the value out of the arena.
+    pub fn into_inner(self) -> T {
+        // SAFETY: the value is initialized, and the handle no longer considers it so
+        unsafe { self.0.into_uninit().as_slice()[0].assume_init_read() }
+    }
+
+    /// Returns the underlying one-element handle.
+    pub fn into_handle(self) -> InitHandle<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for ArenaBox<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the handle always has exactly one element
+        unsafe { self.0.get_unchecked(0) }
+    }
+}
+
+impl<T> DerefMut for ArenaBox<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: the handle always has exactly one element
+        unsafe { self.0.get_unchecked_mut(0) }
+    }
+}
+
+impl<T> AsRef<T> for ArenaBox<T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{
+    AllocError, Chunk, ChunkRef, ChunkSource, GlobalSource, Handle, HandleInner, RingArena,
+    SourceRef,
+    sync::{self, AtomicPtr, AtomicUsize, Mutex, Ordering},
+};
+use std::{num::NonZero, ptr};
+use triomphe::Arc;
+
+/// Cursor value of a chunk which is no longer the front chunk. It's far enough from any valid
+/// offset that bumps of retired chunks always fail, even if they push the cursor further.
+const RETIRED: usize = usize::MAX / 2;
+
+struct ConcurrentChunk<T> {
+    chunk: Chunk<T>,
+    /// Offset of the next allocation into the chunk. Past the chunk length once it's full.
+    cursor: AtomicUsize,
+}
+
+impl<T> ConcurrentChunk<T> {
+    /// Reserves `length` elements, returning the offset of the region.
+    #[inline(always)]
+    fn bump(&self, length: usize, chunk_length: usize) -> Option<usize> {
+        let offset = self.cursor.fetch_add(length, Ordering::Relaxed);
+        (offset + length <= chunk_length).then_some(offset)
+    }
+}
+
+struct State<T> {
+    /// All the allocated chunks. They are behind an `Arc` so they don't move while referenced by
+    /// `front`.
+    chunks: Vec<Arc<ConcurrentChunk<T>>>,
+    /// Maximum number of chunks, `usize::MAX` if unbounded.
+    max_chunks: usize,
+}
+
+/// Arena for short-lived objects which can be allocated from multiple threads at once.
+///
+/// Allocations bump an atomic offset into the front chunk, so they only contend on a lock when
+/// the front chunk is full and has to be replaced.
+pub struct ConcurrentRingArena<T> {
+    chunk_length: usize,
+    /// Where new chunks come from.
+    source: SourceRef,
+    /// The front chunk, which is owned by `state`.
+    front: AtomicPtr<ConcurrentChunk<T>>,
+    state: Mutex<State<T>>,
+}
+
+unsafe impl<T> Send for ConcurrentRingArena<T> where T: Send {}
+unsafe impl<T> Sync for ConcurrentRingArena<T> where T: Sync {}
+
+impl<T> ConcurrentRingArena<T> {
+    pub fn new(chunk_length: NonZero<usize>) -> Self {
+        match Self::with_source(chunk_length, GlobalSource) {
+            Ok(arena) => arena,
+            Err(_) => unreachable!("the global source never runs out of memory"),
+        }
+    }
+
+    /// Creates an arena whose chunks come from `source`. Fails if the source can't provide the
+    /// first chunk.
+    ///
+    /// See [`RingArena::with_source`].
+    pub fn with_source(
+        chunk_length: NonZero<usize>,
+        source: impl ChunkSource + 'static,
+    ) -> Result<Self, AllocError> {
+        let source: SourceRef = std::sync::Arc::new(source);
+        let first = Arc::new(ConcurrentChunk {
+            chunk: Self::new_chunk(chunk_length.get(), &source)?,
+            cursor: AtomicUsize::new(0),
+        });
+
+        Ok(Self {
+            chunk_length: chunk_length.get(),
+            source,
+            front: AtomicPtr::new(Arc::as_ptr(&first).cast_mut()),
+            state: Mutex::new(State {
+                chunks: vec![first],
+                max_chunks: usize::MAX,
+            }),
+        })
+    }
+
+    fn new_chunk(chunk_length: usize, source: &SourceRef) -> Result<Chunk<T>, AllocError> {
+        let layout = RingArena::<T>::chunk_layout(chunk_length, align_of::<T>());
+        // free chunks are found by scanning, so release messages would be useless
+        Chunk::with_layout(layout, source, None)
+    }
+
+    /// Limits the number of chunks this arena may allocate. `None` removes the limit.
+    ///
+    /// Chunks which are already allocated are kept even if they exceed the new limit.
+    pub fn set_max_chunks(&self, max_chunks: Option<NonZero<usize>>) {
+        self.state.lock().unwrap().max_chunks = max_chunks.map_or(usize::MAX, NonZero::get);
+    }
+
+    /// Allocates a `[T]` of the given length.
+    ///
+    /// # Panics
+    /// Panics if [`ConcurrentRingArena::try_allocate`] fails.
+    pub fn allocate(&self, length: usize) -> Handle<T> {
+        match self.try_allocate(length) {
+            Ok(handle) => handle,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Allocates a `[T]` of the given length, failing if the front chunk is full and the arena
+    /// can't allocate another one because of its chunk limit.
+    ///
+    /// Allocations larger than the chunk length are boxed.
+    pub fn try_allocate(&self, length: usize) -> Result<Handle<T>, AllocError> {
+        if length == 0 {
+            return Ok(Handle(HandleInner::empty()));
+        }
+
+        if length > self.chunk_length {
+            return Ok(Handle(HandleInner::Boxed(Box::new_uninit_slice(length))));
+        }
+
+        loop {
+            // SAFETY: chunks are only freed along with the arena
+            let front = unsafe { &*self.front.load(Ordering::Acquire) };
+
+            // take a reference to the chunk before reserving space in it, so that it can't be
+            // recycled while we're at it. pairs with the fence in `rotate`
+            let chunk = ChunkRef::new(&front.chunk.storage);
+            sync::fence(Ordering::SeqCst);
+
+            if let Some(offset) = front.bump(length, self.chunk_length) {
+                return Ok(Handle(HandleInner::Chunk {
+                    // SAFETY: the region was reserved by the bump, so it's within bounds and not
+                    // handed out to anyone else
+                    ptr: unsafe { front.chunk.region(offset, length) },
+                    _chunk: chunk,
+                }));
+            }
+
+            drop(chunk);
+            self.rotate(front)?;
+        }
+    }
+
+    /// Replaces the full front chunk `full` with a free chunk, allocating one if none is free.
+    fn rotate(&self, full: &ConcurrentChunk<T>) -> Result<(), AllocError> {
+        let mut state = self.state.lock().unwrap();
+        // someone else already rotated, maybe recycling `full` itself as the front chunk, in
+        // which case it has room again. failed bumps leave the cursor past the chunk length, so
+        // a front chunk with a lower cursor isn't the one we saw full
+        if !ptr::eq(self.front.load(Ordering::Relaxed), full)
+            || full.cursor.load(Ordering::Relaxed) < self.chunk_length
+        {
+            return Ok(());
+        }
+
+        // once retired, nobody can reserve space in the chunk anymore. after the fence, either
+        // concurrent allocators see it retired or we see their chunk references. pairs with the
+        // fence in `try_allocate`. the cursor is swapped rather than stored here and below, so
+        // the write is ordered with concurrent bumps as a read-modify-write of its own
+        full.cursor.swap(RETIRED, Ordering::Relaxed);
+        sync::fence(Ordering::SeqCst);
+
+        let free = state.chunks.iter().find(|c| c.chunk.is_free());
+        let next = if let Some(chunk) = free {
+            chunk.cursor.swap(0, Ordering::Relaxed);
+            Arc::as_ptr(chunk)
+        } else if state.chunks.len() < state.max_chunks {
+            let chunk = Arc::new(ConcurrentChunk {
+                chunk: Self::new_chunk(self.chunk_length, &self.source)?,
+                cursor: AtomicUsize::new(0),
+            });
+
+            let next = Arc::as_ptr(&chunk);
+            state.chunks.push(chunk);
+            next
+        } else {
+            return Err(AllocError::Exhausted);
+        };
+
+        self.front.store(next.cast_mut(), Ordering::Release);
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{Handle, HandleInner, InitHandle, RingArena};
+use std::{
+    io::{self, BufRead, Cursor, Read, Write},
+    mem,
+    ops::Deref,
+};
+
+/// Growable writer of bytes into a [`RingArena<u8>`], see [`RingArena::writer`].
+///
+/// Bytes are written into the front chunk. If they don't fit, they're moved to a new region large
+/// enough for them.
+pub struct ByteWriter<'a> {
+    arena: &'a mut RingArena<u8>,
+    /// Region the bytes are written into.
+    handle: Handle<u8>,
+    /// Number of bytes written.
+    length: usize,
+}
+
+impl RingArena<u8> {
+    /// Creates a writer which appends bytes to the front chunk of this arena.
+    pub fn writer(&mut self) -> ByteWriter<'_> {
+        let remaining = (self.chunk_length - self.offset).saturating_sub(self.padding());
+        let handle = self.allocate(remaining);
+
+        ByteWriter {
+            arena: self,
+            handle,
+            length: 0,
+        }
+    }
+}
+
+impl ByteWriter<'_> {
+    /// Number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// The bytes written so far.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: the first `length` bytes have been written
+        unsafe { self.handle.as_slice()[..self.length].assume_init_ref() }
+    }
+
+    /// Makes room for `additional` more bytes, moving the written bytes to a new region if needed.
+    fn reserve(&mut self, additional: usize) -> io::Result<()> {
+        let capacity = self.handle.as_slice().len();
+        let needed = self.length + additional;
+        if needed <= capacity {
+            return Ok(());
+        }
+
+        // take a whole chunk if possible, so the region can keep growing in place
+        let chunk_length = self.arena.chunk_length;
+        let new_capacity = if needed <= chunk_length {
+            chunk_length
+        } else {
+            needed.max(capacity * 2)
+        };
+
+        let mut handle = self
+            .arena
+            .try_allocate(new_capacity)
+            .map_err(|e| io::Error::new(io::ErrorKind::OutOfMemory, e))?;
+
+        handle.as_mut_slice()[..self.length]
+            .copy_from_slice(&self.handle.as_slice()[..self.length]);
+        self.handle = handle;
+
+        Ok(())
+    }
+
+    /// Finishes writing, returning the written bytes. Unused space at the end of the region is
+    /// given back to the arena.
+    pub fn freeze(mut self) -> FrozenBytes {
+        let mut handle = mem::replace(&mut self.handle, Handle(HandleInner::empty()));
+        self.arena.shrink(&mut handle, self.length);
+
+        // SAFETY: the handle has been shrunk to the written bytes
+        FrozenBytes(Cursor::new(unsafe { handle.assume_init() }))
+    }
+}
+
+impl Write for ByteWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.reserve(buf.len())?;
+
+        let end = self.length + buf.len();
+        self.handle.as_mut_slice()[self.length..end].write_copy_of_slice(buf);
+        self.length = end;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for ByteWriter<'_> {
+    fn drop(&mut self) {
+        // give back the space if the writer wasn't frozen
+        self.arena.shrink(&mut self.handle, 0);
+    }
+}
+
+/// Immutable bytes written by a [`ByteWriter`]. Reading from it advances an internal cursor,
+/// while [`AsRef`] and [`Deref`] always give access to all of the bytes.
+pub struct FrozenBytes(Cursor<InitHandle<u8>>);
+
+impl FrozenBytes {
+    /// Position of the read cursor.
+    pub fn position(&self) -> usize {
+        self.0.position() as usize
+    }
+
+    /// Moves the read cursor to `position`.
+    pub fn set_position(&mut self, position: usize) {
+        self.0.set_position(position as u64);
+    }
+
+    /// Returns the underlying handle.
+    pub fn into_handle(self) -> InitHandle<u8> {
+        self.0.into_inner()
+    }
+}
+
+/// Converts all of the bytes into [`bytes::Bytes`] without copying, regardless of the position of
+/// the read cursor.
+#[cfg(feature = "bytes")]
+impl From<FrozenBytes> for bytes::Bytes {
+    fn from(bytes: FrozenBytes) -> Self {
+        bytes.into_handle().into()
+    }
+}
+
+impl Deref for FrozenBytes {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl AsRef<[u8]> for FrozenBytes {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self.0.get_ref()
+    }
+}
+
+impl Read for FrozenBytes {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.0.read_exact(buf)
+    }
+}
+
+impl BufRead for FrozenBytes {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.0.consume(amount);
+    }
+}
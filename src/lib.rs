@@ -1,140 +1,1108 @@
-use std::{collections::VecDeque, mem::MaybeUninit, num::NonZero, ptr::NonNull};
+use std::{
+    alloc::Layout,
+    cmp::Reverse,
+    collections::VecDeque,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    num::NonZero,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    time::{Duration, Instant},
+};
+use sync::{AtomicUsize, Ordering};
 use triomphe::Arc;
 
+mod boxed;
+mod concurrent;
+mod io;
+mod pool;
+mod raw;
+mod shared;
+mod source;
+mod sync;
+mod vec;
+
+pub use boxed::ArenaBox;
+pub use concurrent::ConcurrentRingArena;
+pub use io::{ByteWriter, FrozenBytes};
+pub use pool::SharedChunkPool;
+pub use raw::RawRingArena;
+pub use shared::SharedHandle;
+pub use source::{ChunkSource, GlobalSource};
+pub use vec::ArenaVec;
+
 enum HandleInner<T> {
     Chunk {
         ptr: NonNull<[MaybeUninit<T>]>,
-        _count: Arc<()>,
+        _chunk: ChunkRef,
     },
     Boxed(Box<[MaybeUninit<T>]>),
+    /// A slice which needs no memory at all, because it's empty or its elements are zero-sized.
+    /// The pointer is dangling, but aligned.
+    Dangling(NonNull<[MaybeUninit<T>]>),
+}
+
+impl<T> HandleInner<T> {
+    fn empty() -> Self {
+        HandleInner::Dangling(NonNull::slice_from_raw_parts(NonNull::dangling(), 0))
+    }
 }
 
 /// Handle to a `[T]` in a [`RingArena<T>`].
+///
+/// A handle keeps the chunk it points into alive, so it may outlive the arena it
+/// was allocated from.
+///
+/// A handle may own its elements once it's initialized (see [`Handle::assume_init`]), so it can
+/// only be sent to another thread if `T` can:
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<ring_arena::Handle<std::sync::MutexGuard<'static, i32>>>();
+/// ```
 pub struct Handle<T>(HandleInner<T>);
 
-unsafe impl<T> Send for Handle<T> where T: Sync {}
+unsafe impl<T> Send for Handle<T> where T: Send {}
 unsafe impl<T> Sync for Handle<T> where T: Sync {}
 
 impl<T> Handle<T> {
-    /// # Safety
-    /// The arena this handle comes from must still be alive.
     #[inline(always)]
-    pub unsafe fn as_slice(&self) -> &[MaybeUninit<T>] {
+    pub fn as_slice(&self) -> &[MaybeUninit<T>] {
         match &self.0 {
-            // SAFETY: the arena is alive, as stabilished by the method contract
+            // SAFETY: the storage is kept alive by this handle and the region is exclusively
+            // owned by it
             HandleInner::Chunk { ptr, .. } => unsafe { ptr.as_ref() },
             HandleInner::Boxed(b) => b,
+            // SAFETY: the slice covers no memory, and its pointer is aligned
+            HandleInner::Dangling(ptr) => unsafe { ptr.as_ref() },
         }
     }
 
-    /// # Safety
-    /// The arena this handle comes from must still be alive.
     #[inline(always)]
-    pub unsafe fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+    pub fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
         match &mut self.0 {
-            // SAFETY: the arena is alive, as stabilished by the method contract
+            // SAFETY: the storage is kept alive by this handle and the region is exclusively
+            // owned by it
             HandleInner::Chunk { ptr, .. } => unsafe { ptr.as_mut() },
             HandleInner::Boxed(b) => b,
+            // SAFETY: the slice covers no memory, and its pointer is aligned
+            HandleInner::Dangling(ptr) => unsafe { ptr.as_mut() },
         }
     }
 
     /// Whether this handle actually contains a boxed value.
     pub fn is_boxed(&self) -> bool {
         match self.0 {
-            HandleInner::Chunk { .. } => false,
+            HandleInner::Chunk { .. } | HandleInner::Dangling(_) => false,
             HandleInner::Boxed(_) => true,
         }
     }
+
+    /// Converts this handle into an [`InitHandle<T>`], which will drop its elements when dropped.
+    ///
+    /// # Safety
+    /// Every element of this handle must be initialized.
+    #[inline(always)]
+    pub unsafe fn assume_init(self) -> InitHandle<T> {
+        InitHandle(self)
+    }
+
+    /// Splits this handle into `[0, mid)` and `[mid, len)`. Both halves keep the chunk in use.
+    ///
+    /// The second half of a boxed handle is moved into a new box.
+    ///
+    /// # Panics
+    /// Panics if `mid > len`.
+    pub fn split_at(self, mid: usize) -> (Handle<T>, Handle<T>) {
+        let length = self.as_slice().len();
+        assert!(mid <= length, "mid > len");
+
+        match self.0 {
+            HandleInner::Chunk { ptr, _chunk } => {
+                let start = ptr.cast::<MaybeUninit<T>>();
+                // SAFETY: mid is within the bounds of the region
+                let middle = unsafe { start.add(mid) };
+                let second = Handle(HandleInner::Chunk {
+                    ptr: NonNull::slice_from_raw_parts(middle, length - mid),
+                    _chunk: _chunk.clone(),
+                });
+
+                let first = Handle(HandleInner::Chunk {
+                    ptr: NonNull::slice_from_raw_parts(start, mid),
+                    _chunk,
+                });
+
+                (first, second)
+            }
+            HandleInner::Boxed(b) => {
+                let mut first = Vec::from(b);
+                let second = first.split_off(mid);
+
+                (
+                    Handle(HandleInner::Boxed(first.into_boxed_slice())),
+                    Handle(HandleInner::Boxed(second.into_boxed_slice())),
+                )
+            }
+            HandleInner::Dangling(ptr) => {
+                let start = ptr.cast::<MaybeUninit<T>>();
+                // SAFETY: the slice covers no memory, so the offset is zero
+                let middle = unsafe { start.add(mid) };
+
+                (
+                    Handle(HandleInner::Dangling(NonNull::slice_from_raw_parts(
+                        start, mid,
+                    ))),
+                    Handle(HandleInner::Dangling(NonNull::slice_from_raw_parts(
+                        middle,
+                        length - mid,
+                    ))),
+                )
+            }
+        }
+    }
+}
+
+/// Handle to an initialized `[T]` in a [`RingArena<T>`].
+///
+/// The elements are dropped along with the handle, before the chunk is released.
+pub struct InitHandle<T>(Handle<T>);
+
+unsafe impl<T> Send for InitHandle<T> where T: Send {}
+unsafe impl<T> Sync for InitHandle<T> where T: Sync {}
+
+impl<T> InitHandle<T> {
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the elements are initialized, as stabilished by the contract of assume_init
+        unsafe { self.0.as_slice().assume_init_ref() }
+    }
+
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the elements are initialized, as stabilished by the contract of assume_init
+        unsafe { self.0.as_mut_slice().assume_init_mut() }
+    }
+
+    /// Whether this handle actually contains a boxed value.
+    pub fn is_boxed(&self) -> bool {
+        self.0.is_boxed()
+    }
+
+    /// Splits this handle into `[0, mid)` and `[mid, len)`, see [`Handle::split_at`].
+    ///
+    /// # Panics
+    /// Panics if `mid > len`.
+    pub fn split_at(self, mid: usize) -> (InitHandle<T>, InitHandle<T>) {
+        // check before leaking the elements
+        assert!(mid <= self.len(), "mid > len");
+
+        let (first, second) = self.into_uninit().split_at(mid);
+        // SAFETY: both halves are initialized, since the whole handle was
+        unsafe { (first.assume_init(), second.assume_init()) }
+    }
+
+    /// Returns the underlying handle, leaking the elements.
+    fn into_uninit(self) -> Handle<T> {
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again
+        unsafe { std::ptr::read(&this.0) }
+    }
+}
+
+impl<T> Deref for InitHandle<T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> DerefMut for InitHandle<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> AsRef<[T]> for InitHandle<T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+/// Converts the handle into [`bytes::Bytes`] without copying. The chunk is held until the last
+/// clone of the `Bytes` is dropped.
+#[cfg(feature = "bytes")]
+impl From<InitHandle<u8>> for bytes::Bytes {
+    fn from(handle: InitHandle<u8>) -> Self {
+        bytes::Bytes::from_owner(handle)
+    }
+}
+
+impl<T> Drop for InitHandle<T> {
+    fn drop(&mut self) {
+        // SAFETY: the elements are initialized and never used again
+        unsafe { std::ptr::drop_in_place(self.as_mut_slice()) };
+    }
+}
+
+/// Identifies a chunk in release messages. It's the address of the chunk's storage, so it's only
+/// unique among live chunks.
+type ChunkId = usize;
+
+/// Shared reference to the source of a chunk. Unlike the chunk storage, it's a trait object, so
+/// it's behind a standard `Arc`.
+type SourceRef = std::sync::Arc<dyn ChunkSource>;
+
+/// Memory backing a chunk. It is shared between the arena and every handle into the chunk,
+/// and is only freed once all of them are gone.
+struct ChunkStorage {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    /// Where the memory came from, and goes back to.
+    source: SourceRef,
+    /// Number of [`ChunkRef`]s to this chunk.
+    live: AtomicUsize,
+    /// For dedicated chunks, the arena's count of live dedicated chunks, which is decremented
+    /// once this one is released.
+    dedicated: Option<Arc<AtomicUsize>>,
+    /// Notified with the ID of this chunk whenever the last [`ChunkRef`] to it is dropped, if
+    /// anyone is interested.
+    release: Option<flume::Sender<ChunkId>>,
+}
+
+// SAFETY: the storage is just uninitialized memory, the handles into it are responsible for
+// upholding the thread safety of the values they contain
+unsafe impl Send for ChunkStorage {}
+unsafe impl Sync for ChunkStorage {}
+
+impl ChunkStorage {
+    fn new(
+        layout: Layout,
+        source: &SourceRef,
+        release: Option<flume::Sender<ChunkId>>,
+    ) -> Result<Self, AllocError> {
+        let ptr = if layout.size() == 0 {
+            // any aligned pointer is valid for zero-sized accesses
+            NonNull::without_provenance(NonZero::new(layout.align()).unwrap())
+        } else {
+            source.allocate(layout).ok_or(AllocError::Exhausted)?
+        };
+
+        Ok(Self {
+            ptr,
+            layout,
+            source: source.clone(),
+            live: AtomicUsize::new(0),
+            dedicated: None,
+            release,
+        })
+    }
+}
+
+impl Drop for ChunkStorage {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            // SAFETY: ptr was allocated from this source with this layout in ChunkStorage::new
+            unsafe { self.source.deallocate(self.ptr, self.layout) };
+        }
+    }
+}
+
+/// A handle's reference to the chunk it points into.
+struct ChunkRef(Arc<ChunkStorage>);
+
+impl ChunkRef {
+    fn new(storage: &Arc<ChunkStorage>) -> Self {
+        storage.live.fetch_add(1, Ordering::Relaxed);
+        Self(storage.clone())
+    }
+}
+
+impl Clone for ChunkRef {
+    fn clone(&self) -> Self {
+        Self::new(&self.0)
+    }
+}
+
+impl Drop for ChunkRef {
+    fn drop(&mut self) {
+        if self.0.live.fetch_sub(1, Ordering::Release) == 1 {
+            if let Some(dedicated) = &self.0.dedicated {
+                // before the release message, so that whoever it wakes sees the chunk gone
+                dedicated.fetch_sub(1, Ordering::Release);
+            }
+
+            // the arena might be gone already, in which case nobody cares
+            if let Some(release) = &self.0.release {
+                let _ = release.send(Arc::as_ptr(&self.0).addr());
+            }
+        }
+    }
 }
 
 struct Chunk<T> {
-    storage: Box<[MaybeUninit<T>]>,
-    allocated: Arc<()>,
+    storage: Arc<ChunkStorage>,
+    /// Rotation at which this chunk was last seen in use, either as the front chunk or with live
+    /// handles into it.
+    last_used: usize,
+    /// Sequence number of the last release of this chunk the arena knows about.
+    released: usize,
+    _phantom: PhantomData<T>,
 }
 
 impl<T> Chunk<T> {
-    pub fn new(length: usize) -> Self {
-        let mut storage = Vec::with_capacity(length);
-        // SAFETY: MaybeUninit is always considered initialized
-        unsafe { storage.set_len(length) };
+    /// Creates a chunk whose storage has the given layout, which must fit a whole number of `T`s.
+    fn with_layout(
+        layout: Layout,
+        source: &SourceRef,
+        release: Option<flume::Sender<ChunkId>>,
+    ) -> Result<Self, AllocError> {
+        Ok(Self {
+            storage: Arc::new(ChunkStorage::new(layout, source, release)?),
+            last_used: 0,
+            released: 0,
+            _phantom: PhantomData,
+        })
+    }
 
-        Self {
-            storage: storage.into_boxed_slice(),
-            allocated: Arc::new(()),
+    #[inline(always)]
+    fn id(&self) -> ChunkId {
+        Arc::as_ptr(&self.storage).addr()
+    }
+
+    /// Whether there are no handles into this chunk.
+    #[inline(always)]
+    fn is_free(&self) -> bool {
+        self.storage.live.load(Ordering::Acquire) == 0
+    }
+
+    /// Pointer to the `[offset, offset + length)` region of this chunk.
+    ///
+    /// # Safety
+    /// The region must be within the bounds of the chunk.
+    #[inline(always)]
+    unsafe fn region(&self, offset: usize, length: usize) -> NonNull<[MaybeUninit<T>]> {
+        // SAFETY: the region is within bounds, as stabilished by the method contract
+        let start = unsafe { self.storage.ptr.cast::<MaybeUninit<T>>().add(offset) };
+        NonNull::slice_from_raw_parts(start, length)
+    }
+}
+
+/// Error returned by fallible allocations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocError {
+    /// The arena reached its chunk limit and none of its chunks are free.
+    Exhausted,
+    /// The allocation is larger than the chunk length and the arena's [`OversizePolicy`] is
+    /// [`OversizePolicy::Error`].
+    Oversized,
+}
+
+impl std::fmt::Display for AllocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AllocError::Exhausted => f.write_str("ring arena exhausted"),
+            AllocError::Oversized => f.write_str("allocation larger than the chunk length"),
         }
     }
 }
 
+impl std::error::Error for AllocError {}
+
+/// What a [`RingArena`] does with allocations larger than its chunk length.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OversizePolicy {
+    /// Allocate a boxed slice, see [`Handle::is_boxed`].
+    #[default]
+    Box,
+    /// Allocate a dedicated chunk of the exact length. The chunk counts towards the chunk limit of
+    /// the arena, or of its [`SharedChunkPool`], until it is released.
+    Dedicated,
+    /// Fail with [`AllocError::Oversized`].
+    Error,
+    /// Panic in debug builds, allocate a boxed slice otherwise.
+    DebugPanic,
+}
+
+/// Statistics about a [`RingArena`], see [`RingArena::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RingArenaStats {
+    /// Number of chunks owned by the arena.
+    pub chunks: usize,
+    /// Total capacity of the chunks, in bytes.
+    pub capacity_bytes: usize,
+    /// Offset into the front chunk, in elements.
+    pub front_offset: usize,
+    /// Number of chunks with live handles into them.
+    pub busy_chunks: usize,
+    /// Total number of successful allocations.
+    pub allocations: u64,
+    /// Number of allocations which fell back to a boxed slice.
+    pub boxed_allocations: u64,
+    /// Number of dedicated chunks with live handles into them, see [`OversizePolicy::Dedicated`].
+    /// For arenas created from a [`SharedChunkPool`], these are the dedicated chunks of all of
+    /// its arenas.
+    pub dedicated_chunks: usize,
+    /// Number of allocations which got a dedicated chunk.
+    pub dedicated_allocations: u64,
+    /// Number of chunks allocated because no existing chunk could be reused.
+    pub chunks_created: u64,
+}
+
+/// Running counters of a [`RingArena`].
+#[derive(Default)]
+struct Counters {
+    allocations: u64,
+    boxed_allocations: u64,
+    dedicated_allocations: u64,
+    chunks_created: u64,
+}
+
 /// Arena for short-lived objects.
 pub struct RingArena<T> {
     chunk_length: usize,
+    /// Alignment of the start of each chunk, in bytes.
+    align: usize,
+    /// Whether allocations are padded to start at `align` too.
+    align_allocations: bool,
+    /// Where new chunks come from.
+    source: SourceRef,
+    /// Maximum number of chunks, `usize::MAX` if unbounded.
+    max_chunks: usize,
     /// All the allocated chunks.
     chunks: VecDeque<Chunk<T>>,
+    oversize_policy: OversizePolicy,
+    /// Number of live dedicated chunks of oversized allocations, shared with the pool if any. The
+    /// chunks themselves are only owned by their handles, so they're freed along with the last
+    /// one.
+    dedicated: Arc<AtomicUsize>,
     /// Offset into the front chunk.
     offset: usize,
+    /// Number of times the front chunk has been replaced.
+    rotations: usize,
+    /// Number of rotations after which idle chunks are freed, if any.
+    trim_after: Option<usize>,
+    /// Number of chunk releases noted so far.
+    releases: usize,
+    counters: Counters,
+    /// Sender given to new chunks, through which they signal they've been released.
+    release_tx: flume::Sender<ChunkId>,
+    /// Receives a message whenever a chunk is released.
+    release_rx: flume::Receiver<ChunkId>,
+    /// Pool the chunks come from, if any.
+    pool: Option<SharedChunkPool<T>>,
 }
 
 impl<T> RingArena<T> {
     pub fn new(chunk_length: NonZero<usize>) -> Self {
-        let first = Chunk::new(chunk_length.get());
+        Self::with_alignment(chunk_length, align_of::<T>())
+    }
+
+    /// Creates an arena whose chunks start at an address aligned to `align` bytes, or to `T` if
+    /// that's stricter. Dedicated chunks are aligned as well, but boxed allocations are only
+    /// aligned to `T`, see [`OversizePolicy`].
+    ///
+    /// Allocations are only aligned to `T` within a chunk, unless
+    /// [`RingArena::set_align_allocations`] is enabled.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two.
+    pub fn with_alignment(chunk_length: NonZero<usize>, align: usize) -> Self {
+        match Self::with_source(chunk_length, align, GlobalSource) {
+            Ok(arena) => arena,
+            Err(_) => unreachable!("the global source never runs out of memory"),
+        }
+    }
+
+    /// Creates an arena whose chunks come from `source`, aligned as with
+    /// [`RingArena::with_alignment`]. Fails if the source can't provide the first chunk.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two.
+    pub fn with_source(
+        chunk_length: NonZero<usize>,
+        align: usize,
+        source: impl ChunkSource + 'static,
+    ) -> Result<Self, AllocError> {
+        assert!(align.is_power_of_two(), "alignment is not a power of two");
+        let align = align.max(align_of::<T>());
+
+        let source: SourceRef = std::sync::Arc::new(source);
+        let (release_tx, release_rx) = flume::unbounded();
+        let first = Chunk::with_layout(
+            Self::chunk_layout(chunk_length.get(), align),
+            &source,
+            Some(release_tx.clone()),
+        )?;
+
+        Ok(Self::from_parts(
+            chunk_length.get(),
+            first,
+            release_tx,
+            release_rx,
+            Arc::new(AtomicUsize::new(0)),
+            None,
+        ))
+    }
+
+    /// Layout of a chunk of `length` elements aligned to `align` bytes.
+    fn chunk_layout(length: usize, align: usize) -> Layout {
+        Layout::array::<T>(length)
+            .and_then(|layout| layout.align_to(align))
+            .expect("chunk too large")
+    }
+
+    fn from_parts(
+        chunk_length: usize,
+        first: Chunk<T>,
+        release_tx: flume::Sender<ChunkId>,
+        release_rx: flume::Receiver<ChunkId>,
+        dedicated: Arc<AtomicUsize>,
+        pool: Option<SharedChunkPool<T>>,
+    ) -> Self {
         Self {
-            chunk_length: chunk_length.get(),
+            chunk_length,
+            align: first.storage.layout.align(),
+            source: first.storage.source.clone(),
+            align_allocations: false,
+            max_chunks: usize::MAX,
             chunks: VecDeque::from([first]),
+            oversize_policy: OversizePolicy::default(),
+            dedicated,
             offset: 0,
+            rotations: 0,
+            trim_after: None,
+            releases: 0,
+            counters: Counters::default(),
+            release_tx,
+            release_rx,
+            pool,
         }
     }
 
+    /// Limits the number of chunks this arena may allocate. `None` removes the limit.
+    ///
+    /// Chunks which are already allocated are kept even if they exceed the new limit. For arenas
+    /// created from a [`SharedChunkPool`], only the limit of the pool applies to its chunks.
+    pub fn set_max_chunks(&mut self, max_chunks: Option<NonZero<usize>>) {
+        self.max_chunks = max_chunks.map_or(usize::MAX, NonZero::get);
+    }
+
+    /// Limits the memory used by the chunks of this arena to roughly `max_bytes`, rounded down to
+    /// a whole number of chunks (but at least one). `None` removes the limit.
+    ///
+    /// See [`RingArena::set_max_chunks`].
+    pub fn set_max_bytes(&mut self, max_bytes: Option<NonZero<usize>>) {
+        let chunk_bytes = self.chunk_length * size_of::<T>();
+        self.max_chunks = match max_bytes {
+            Some(max_bytes) if chunk_bytes != 0 => (max_bytes.get() / chunk_bytes).max(1),
+            _ => usize::MAX,
+        };
+    }
+
+    /// Sets what this arena does with allocations larger than its chunk length.
+    pub fn set_oversize_policy(&mut self, policy: OversizePolicy) {
+        self.oversize_policy = policy;
+    }
+
+    /// Pads every allocation so that it starts at the chunk alignment given to
+    /// [`RingArena::with_alignment`], wasting up to that many bytes per allocation.
+    pub fn set_align_allocations(&mut self, enabled: bool) {
+        self.align_allocations = enabled;
+    }
+
+    /// Returns statistics about this arena.
+    pub fn stats(&self) -> RingArenaStats {
+        RingArenaStats {
+            chunks: self.chunks.len(),
+            capacity_bytes: self.chunks.len() * self.chunk_length * size_of::<T>(),
+            front_offset: self.offset,
+            busy_chunks: self.chunks.iter().filter(|c| !c.is_free()).count(),
+            allocations: self.counters.allocations,
+            boxed_allocations: self.counters.boxed_allocations,
+            dedicated_chunks: self.dedicated.load(Ordering::Acquire),
+            dedicated_allocations: self.counters.dedicated_allocations,
+            chunks_created: self.counters.chunks_created,
+        }
+    }
+
+    /// Frees idle chunks, starting from the back, until at most `max_chunks` are left. The front
+    /// chunk and chunks with live handles are never freed, so more chunks might remain.
+    pub fn shrink_to(&mut self, max_chunks: usize) {
+        let mut index = self.chunks.len();
+        while self.chunks.len() > max_chunks && index > 1 {
+            index -= 1;
+            if self.chunks[index].is_free() {
+                self.chunks.remove(index);
+            }
+        }
+    }
+
+    /// Frees every idle chunk except the front one.
+    pub fn trim(&mut self) {
+        self.shrink_to(1);
+    }
+
+    /// Automatically frees chunks which stay idle for `rotations` rotations of the front chunk,
+    /// counting from the last rotation at which they were in use. `None` disables automatic
+    /// trimming.
+    ///
+    /// While enabled, free chunks are reused most recently used first rather than in the order
+    /// they were released, so that the chunks left over from a burst of allocations stay idle
+    /// and get freed.
+    pub fn set_trim_after(&mut self, rotations: Option<NonZero<usize>>) {
+        self.trim_after = rotations.map(NonZero::get);
+    }
+
     /// # Safety
     /// `length` elements must fit within the remaining space of the front chunk.
     unsafe fn allocate_unchecked(&mut self, length: usize) -> Handle<T> {
-        let front = self.chunks.front_mut().unwrap();
+        let front = self.chunks.front().unwrap();
         let handle = Handle(HandleInner::Chunk {
-            ptr: NonNull::slice_from_raw_parts(
-                NonNull::from_mut(&mut front.storage[self.offset]),
-                length,
-            ),
-            _count: front.allocated.clone(),
+            // SAFETY: the region fits in the chunk, as stabilished by the method contract
+            ptr: unsafe { front.region(self.offset, length) },
+            _chunk: ChunkRef::new(&front.storage),
         });
 
         self.offset += length;
+        self.counters.allocations += 1;
         handle
     }
 
-    pub fn allocate(&mut self, length: usize) -> Handle<T> {
-        if length == 0 || length > self.chunk_length {
-            return Handle(HandleInner::Boxed(Box::new_uninit_slice(length)));
+    /// Number of elements to skip in the front chunk so that the next allocation is aligned, see
+    /// [`RingArena::set_align_allocations`].
+    fn padding(&self) -> usize {
+        if !self.align_allocations || size_of::<T>() == 0 {
+            // zero-sized values are always aligned
+            return 0;
         }
 
-        let remaining = self.chunk_length - self.offset;
-        if remaining >= length {
-            unsafe { self.allocate_unchecked(length) }
+        // chunks start aligned, so an offset is aligned iff its size in bytes is a multiple of
+        // the alignment. `align` is a power of two, so the gcd is its largest common power of two
+        let gcd = (1 << size_of::<T>().trailing_zeros()).min(self.align);
+        let step = self.align / gcd;
+        self.offset.next_multiple_of(step) - self.offset
+    }
+
+    /// Number of chunks counting towards the chunk limit.
+    fn chunk_count(&self) -> usize {
+        self.chunks.len() + self.dedicated.load(Ordering::Acquire)
+    }
+
+    fn allocate_oversized(&mut self, length: usize) -> Result<Handle<T>, AllocError> {
+        match self.oversize_policy {
+            OversizePolicy::Box => (),
+            OversizePolicy::Dedicated => return self.allocate_dedicated(length, self.align),
+            OversizePolicy::Error => return Err(AllocError::Oversized),
+            OversizePolicy::DebugPanic => {
+                debug_assert!(false, "allocation larger than the chunk length")
+            }
+        }
+
+        self.counters.allocations += 1;
+        self.counters.boxed_allocations += 1;
+        Ok(Handle(HandleInner::Boxed(Box::new_uninit_slice(length))))
+    }
+
+    /// Allocates a chunk of exactly `length` elements, aligned to `align` bytes, for a single
+    /// allocation.
+    fn allocate_dedicated(&mut self, length: usize, align: usize) -> Result<Handle<T>, AllocError> {
+        if let Some(pool) = &self.pool {
+            // the pool checks its own limit and counts the chunk, as the counter is shared
+            pool.reserve_dedicated()?;
+        } else if self.chunk_count() >= self.max_chunks {
+            return Err(AllocError::Exhausted);
+        } else {
+            self.dedicated.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let storage = ChunkStorage::new(
+            Self::chunk_layout(length, align),
+            &self.source,
+            Some(self.release_tx.clone()),
+        );
+        let mut storage = storage.inspect_err(|_| {
+            self.dedicated.fetch_sub(1, Ordering::Relaxed);
+        })?;
+        storage.dedicated = Some(self.dedicated.clone());
+
+        // the handle is the only owner of the chunk
+        let storage = Arc::new(storage);
+        let handle = Handle(HandleInner::Chunk {
+            ptr: NonNull::slice_from_raw_parts(storage.ptr.cast(), length),
+            _chunk: ChunkRef::new(&storage),
+        });
+
+        self.counters.allocations += 1;
+        self.counters.dedicated_allocations += 1;
+        Ok(handle)
+    }
+
+    /// Whether `handle` is the last allocation in the front chunk of this arena.
+    fn is_last(&self, handle: &Handle<T>) -> bool {
+        let HandleInner::Chunk { ptr, _chunk } = &handle.0 else {
+            return false;
+        };
+
+        let front = self.chunks.front().unwrap();
+        // SAFETY: both pointers are within the bounds of (or one past) their chunks
+        let (front_end, handle_end) = unsafe {
+            (
+                front.region(self.offset, 0).cast::<MaybeUninit<T>>(),
+                ptr.cast::<MaybeUninit<T>>().add(ptr.len()),
+            )
+        };
+
+        Arc::ptr_eq(&_chunk.0, &front.storage) && handle_end == front_end
+    }
+
+    /// Grows `handle` to `length` elements without moving it, which is only possible if it's the
+    /// last allocation in the front chunk and there's enough space left after it. Returns whether
+    /// the handle was grown.
+    ///
+    /// # Panics
+    /// Panics if `length` is smaller than the length of the handle.
+    pub fn grow_in_place(&mut self, handle: &mut Handle<T>, length: usize) -> bool {
+        let old_length = handle.as_slice().len();
+        assert!(length >= old_length, "growing handle to a smaller length");
+
+        let additional = length - old_length;
+        if !self.is_last(handle) || self.chunk_length - self.offset < additional {
+            return false;
+        }
+
+        if let HandleInner::Chunk { ptr, .. } = &mut handle.0 {
+            *ptr = NonNull::slice_from_raw_parts(ptr.cast(), length);
+        }
+
+        self.offset += additional;
+        true
+    }
+
+    /// Grows `handle` to `length` elements, in place if possible. Otherwise, its elements are
+    /// moved into a new allocation, failing if [`RingArena::try_allocate`] does.
+    ///
+    /// # Panics
+    /// Panics if `length` is smaller than the length of the handle.
+    pub fn try_grow(&mut self, handle: &mut Handle<T>, length: usize) -> Result<(), AllocError> {
+        let old_length = handle.as_slice().len();
+        self.try_grow_prefix(handle, length, old_length)
+    }
+
+    /// Like [`RingArena::try_grow`], but only the first `prefix` elements are moved if the handle
+    /// can't grow in place.
+    pub(crate) fn try_grow_prefix(
+        &mut self,
+        handle: &mut Handle<T>,
+        length: usize,
+        prefix: usize,
+    ) -> Result<(), AllocError> {
+        debug_assert!(prefix <= handle.as_slice().len());
+        if self.grow_in_place(handle, length) {
+            return Ok(());
+        }
+
+        let mut grown = self.try_allocate(length)?;
+        // SAFETY: the regions belong to different handles, so they don't overlap, and both are at
+        // least `prefix` elements long
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                handle.as_slice().as_ptr(),
+                grown.as_mut_slice().as_mut_ptr(),
+                prefix,
+            )
+        };
+
+        *handle = grown;
+        Ok(())
+    }
+
+    /// Shrinks `handle` to its first `length` elements. If it's the last allocation in the front
+    /// chunk, the space is given back to the arena.
+    ///
+    /// Elements past `length` are forgotten.
+    ///
+    /// # Panics
+    /// Panics if `length` is larger than the length of the handle.
+    pub fn shrink(&mut self, handle: &mut Handle<T>, length: usize) {
+        let old_length = handle.as_slice().len();
+        assert!(length <= old_length, "shrinking handle beyond its length");
+
+        if self.is_last(handle) {
+            self.offset -= old_length - length;
+        }
+
+        match &mut handle.0 {
+            HandleInner::Chunk { ptr, .. } | HandleInner::Dangling(ptr) => {
+                *ptr = NonNull::slice_from_raw_parts(ptr.cast(), length);
+            }
+            HandleInner::Boxed(b) => {
+                let mut vec = Vec::from(std::mem::take(b));
+                vec.truncate(length);
+                *b = vec.into_boxed_slice();
+            }
+        }
+    }
+
+    /// Notes that the chunk with the given ID was released, so that chunks are reused in the
+    /// order they're released.
+    fn note_release(&mut self, id: ChunkId) {
+        if let Some(chunk) = self.chunks.iter_mut().find(|chunk| chunk.id() == id) {
+            self.releases += 1;
+            chunk.released = self.releases;
+        }
+    }
+
+    /// Moves the full front chunk to the back and makes the free chunk which was released the
+    /// longest ago the new front, allocating one if none is free. With automatic trimming, the
+    /// most recently used free chunk is taken instead, see [`RingArena::set_trim_after`].
+    fn rotate(&mut self) -> Result<(), AllocError> {
+        if let Some(pool) = &self.pool {
+            // pooled arenas only hold their front chunk
+            pool.rotate(&mut self.chunks[0])?;
         } else {
-            // chunk is full, move front chunk to the back
-            let full = self.chunks.pop_front().unwrap();
-            self.chunks.push_back(full);
-            self.offset = 0;
-
-            let front = self.chunks.front_mut().unwrap();
-            if front.allocated.is_unique() {
-                unsafe { self.allocate_unchecked(length) }
+            while let Ok(id) = self.release_rx.try_recv() {
+                self.note_release(id);
+            }
+
+            // there are usually few chunks, so a linear scan is cheap enough
+            let len = self.chunks.len();
+            let free = (0..len).filter(|&i| self.chunks[i].is_free());
+            let free = if self.trim_after.is_some() {
+                // ties go to the chunk closest to the front, which is the warmest
+                free.max_by_key(|&i| (self.chunks[i].last_used, Reverse(i)))
             } else {
-                let chunk = Chunk::new(self.chunk_length);
+                free.min_by_key(|&i| self.chunks[i].released)
+            };
+
+            if let Some(index) = free {
+                self.chunks.rotate_left(1);
+                let chunk = self.chunks.remove((index + len - 1) % len).unwrap();
                 self.chunks.push_front(chunk);
-                unsafe { self.allocate_unchecked(length) }
+            } else if self.chunk_count() < self.max_chunks {
+                let layout = Self::chunk_layout(self.chunk_length, self.align);
+                let chunk =
+                    Chunk::with_layout(layout, &self.source, Some(self.release_tx.clone()))?;
+                self.chunks.rotate_left(1);
+                self.chunks.push_front(chunk);
+                self.counters.chunks_created += 1;
+            } else {
+                return Err(AllocError::Exhausted);
+            }
+        }
+
+        self.offset = 0;
+        self.rotations += 1;
+        self.chunks[0].last_used = self.rotations;
+
+        if let Some(trim_after) = self.trim_after {
+            let rotations = self.rotations;
+            self.chunks.retain_mut(|chunk| {
+                if !chunk.is_free() {
+                    // idle time only counts once the last handle is gone
+                    chunk.last_used = rotations;
+                }
+
+                rotations - chunk.last_used <= trim_after
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Allocates a `[T]` of the given length.
+    ///
+    /// # Panics
+    /// Panics if [`RingArena::try_allocate`] fails.
+    pub fn allocate(&mut self, length: usize) -> Handle<T> {
+        match self.try_allocate(length) {
+            Ok(handle) => handle,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Allocates a `[T]` of the given length, failing if the front chunk is full and the arena
+    /// can't allocate another one because of its chunk limit, or if the allocation is oversized
+    /// and the arena's [`OversizePolicy`] is [`OversizePolicy::Error`].
+    pub fn try_allocate(&mut self, length: usize) -> Result<Handle<T>, AllocError> {
+        if length == 0 {
+            self.counters.allocations += 1;
+            return Ok(Handle(HandleInner::empty()));
+        }
+
+        if length > self.chunk_length {
+            return self.allocate_oversized(length);
+        }
+
+        let remaining = self.chunk_length - self.offset;
+        if remaining < self.padding() + length {
+            // chunk is full, move on to the next one
+            self.rotate()?;
+        }
+
+        self.offset += self.padding();
+        unsafe { Ok(self.allocate_unchecked(length)) }
+    }
+
+    /// Allocates a `[T]` of the given length, waiting for a chunk to be released if the arena is
+    /// exhausted.
+    ///
+    /// Handles are released as they're dropped, possibly on other threads. If every handle
+    /// keeping the arena exhausted belongs to the current thread, this blocks forever.
+    ///
+    /// # Panics
+    /// Panics if [`RingArena::try_allocate`] fails with anything but [`AllocError::Exhausted`].
+    pub fn allocate_blocking(&mut self, length: usize) -> Handle<T> {
+        loop {
+            match self.try_allocate(length) {
+                Ok(handle) => return handle,
+                Err(AllocError::Exhausted) => (),
+                Err(e) => panic!("{e}"),
+            }
+
+            // the arena keeps a sender, so the channel is never disconnected
+            if let Ok(id) = self.release_rx.recv() {
+                self.note_release(id);
+            }
+        }
+    }
+
+    /// Allocates a `[T]` of the given length, waiting up to `timeout` for a chunk to be released
+    /// if the arena is exhausted.
+    ///
+    /// See [`RingArena::allocate_blocking`].
+    pub fn allocate_timeout(
+        &mut self,
+        length: usize,
+        timeout: Duration,
+    ) -> Result<Handle<T>, AllocError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.try_allocate(length) {
+                Ok(handle) => return Ok(handle),
+                Err(AllocError::Exhausted) => match self.release_rx.recv_deadline(deadline) {
+                    Ok(id) => self.note_release(id),
+                    Err(_) => return Err(AllocError::Exhausted),
+                },
+                Err(e) => return Err(e),
             }
         }
     }
+
+    /// Allocates a `[T]` of the given length, asynchronously waiting for a chunk to be released if
+    /// the arena is exhausted.
+    ///
+    /// See [`RingArena::allocate_blocking`].
+    #[cfg(feature = "async")]
+    pub async fn allocate_async(&mut self, length: usize) -> Handle<T> {
+        loop {
+            match self.try_allocate(length) {
+                Ok(handle) => return handle,
+                Err(AllocError::Exhausted) => (),
+                Err(e) => panic!("{e}"),
+            }
+
+            // the arena keeps a sender, so the channel is never disconnected
+            if let Ok(id) = self.release_rx.recv_async().await {
+                self.note_release(id);
+            }
+        }
+    }
+
+    /// Allocates a `[T]` of the given length, initializing each element with `f(index)`.
+    ///
+    /// If `f` panics, the elements initialized so far are dropped.
+    pub fn allocate_with(&mut self, length: usize, f: impl FnMut(usize) -> T) -> InitHandle<T> {
+        let mut handle = self.allocate(length);
+        let mut guard = FillGuard {
+            slice: handle.as_mut_slice(),
+            initialized: 0,
+        };
+
+        guard.fill(f);
+        std::mem::forget(guard);
+
+        // SAFETY: the guard initialized every element
+        unsafe { handle.assume_init() }
+    }
+
+    /// Allocates a copy of `values`.
+    pub fn allocate_copy(&mut self, values: &[T]) -> InitHandle<T>
+    where
+        T: Copy,
+    {
+        let mut handle = self.allocate(values.len());
+        handle.as_mut_slice().write_copy_of_slice(values);
+
+        // SAFETY: every element was just initialized
+        unsafe { handle.assume_init() }
+    }
+
+    /// Allocates a clone of `values`.
+    ///
+    /// If cloning panics, the elements cloned so far are dropped.
+    pub fn allocate_clone(&mut self, values: &[T]) -> InitHandle<T>
+    where
+        T: Clone,
+    {
+        self.allocate_with(values.len(), |i| values[i].clone())
+    }
+
+    /// Allocates a `[T]` with the elements of `iter`.
+    ///
+    /// If the iterator panics, the elements yielded so far are dropped.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields fewer elements than its reported length. Extra elements are
+    /// ignored.
+    pub fn allocate_from_exact_iter<I>(&mut self, iter: I) -> InitHandle<T>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut iter = iter.into_iter();
+        self.allocate_with(iter.len(), |_| {
+            iter.next()
+                .expect("iterator yielded fewer elements than its reported length")
+        })
+    }
 }
 
-unsafe impl<T> Send for RingArena<T> where T: Send {}
-unsafe impl<T> Sync for RingArena<T> where T: Sync {}
+/// Initializes a `[MaybeUninit<T>]` front to back, dropping the initialized prefix if dropped
+/// before [`FillGuard::fill`] completes (i.e. on panic).
+struct FillGuard<'a, T> {
+    slice: &'a mut [MaybeUninit<T>],
+    initialized: usize,
+}
+
+impl<T> FillGuard<'_, T> {
+    fn fill(&mut self, mut f: impl FnMut(usize) -> T) {
+        while self.initialized < self.slice.len() {
+            self.slice[self.initialized].write(f(self.initialized));
+            self.initialized += 1;
+        }
+    }
+}
+
+impl<T> Drop for FillGuard<'_, T> {
+    fn drop(&mut self) {
+        // SAFETY: the first `initialized` elements have been initialized
+        unsafe { self.slice[..self.initialized].assume_init_drop() };
+    }
+}
 
 impl<T> Drop for RingArena<T> {
     fn drop(&mut self) {
-        for chunk in &self.chunks {
-            if !chunk.allocated.is_unique() {
-                panic!("ring arena dropped while allocations exist");
-            }
+        if let Some(pool) = &self.pool
+            && let Some(front) = self.chunks.pop_front()
+        {
+            pool.retire(front);
         }
     }
 }
+
+unsafe impl<T> Send for RingArena<T> where T: Send {}
+unsafe impl<T> Sync for RingArena<T> where T: Sync {}
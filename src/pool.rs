@@ -0,0 +1,222 @@
+use crate::{
+    AllocError, Chunk, ChunkId, ChunkSource, GlobalSource, RingArena, SourceRef,
+    sync::{AtomicUsize, Ordering},
+};
+use std::{mem, num::NonZero, sync::Mutex};
+use triomphe::Arc;
+
+struct State<T> {
+    /// Chunks which aren't the front chunk of any arena, some of which might still be in use.
+    retired: Vec<Chunk<T>>,
+    /// IDs of the chunks held by arenas as their front chunk.
+    fronts: Vec<ChunkId>,
+    /// Number of chunks allocated by the pool, including the ones held by arenas, but not the
+    /// dedicated chunks of its arenas.
+    chunks: usize,
+    /// Maximum number of chunks, `usize::MAX` if unbounded.
+    max_chunks: usize,
+}
+
+struct Pool<T> {
+    chunk_length: usize,
+    /// Where new chunks come from.
+    source: SourceRef,
+    state: Mutex<State<T>>,
+    /// Number of live dedicated chunks of the arenas of this pool, which count towards its chunk
+    /// limit.
+    dedicated: Arc<AtomicUsize>,
+    release_tx: flume::Sender<ChunkId>,
+    release_rx: flume::Receiver<ChunkId>,
+}
+
+/// Pool of chunks shared by multiple [`RingArena`]s, usually one per thread.
+///
+/// Each arena only holds its front chunk and allocates from it without contention. Full chunks
+/// are retired to the pool, and once every handle into them is dropped (on whichever thread),
+/// they can be picked up again by any arena.
+///
+/// Free chunks are picked up in no particular order. Only an arena without a pool reuses its
+/// chunks in the order they were released.
+pub struct SharedChunkPool<T>(Arc<Pool<T>>);
+
+impl<T> Clone for SharedChunkPool<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+unsafe impl<T> Send for SharedChunkPool<T> where T: Send {}
+unsafe impl<T> Sync for SharedChunkPool<T> where T: Sync {}
+
+impl<T> SharedChunkPool<T> {
+    pub fn new(chunk_length: NonZero<usize>) -> Self {
+        Self::with_source(chunk_length, GlobalSource)
+    }
+
+    /// Creates a pool whose chunks come from `source`. Arenas created from the pool fail to
+    /// allocate if the source can't provide a chunk.
+    ///
+    /// See [`RingArena::with_source`].
+    pub fn with_source(chunk_length: NonZero<usize>, source: impl ChunkSource + 'static) -> Self {
+        let (release_tx, release_rx) = flume::unbounded();
+        Self(Arc::new(Pool {
+            chunk_length: chunk_length.get(),
+            source: std::sync::Arc::new(source),
+            state: Mutex::new(State {
+                retired: Vec::new(),
+                fronts: Vec::new(),
+                chunks: 0,
+                max_chunks: usize::MAX,
+            }),
+            dedicated: Arc::new(AtomicUsize::new(0)),
+            release_tx,
+            release_rx,
+        }))
+    }
+
+    /// Limits the number of chunks this pool may allocate, across all of its arenas. `None`
+    /// removes the limit.
+    ///
+    /// Dedicated chunks of the arenas count towards the limit until they're released, see
+    /// [`OversizePolicy::Dedicated`](crate::OversizePolicy::Dedicated).
+    ///
+    /// Chunks which are already allocated are kept even if they exceed the new limit.
+    pub fn set_max_chunks(&self, max_chunks: Option<NonZero<usize>>) {
+        self.0.state.lock().unwrap().max_chunks = max_chunks.map_or(usize::MAX, NonZero::get);
+    }
+
+    /// Creates an arena which takes its chunks from this pool, failing if the pool is exhausted.
+    pub fn arena(&self) -> Result<RingArena<T>, AllocError> {
+        let mut state = self.0.state.lock().unwrap();
+        let first = self.take_free(&mut state)?;
+        drop(state);
+
+        Ok(RingArena::from_parts(
+            self.0.chunk_length,
+            first,
+            self.0.release_tx.clone(),
+            self.0.release_rx.clone(),
+            self.0.dedicated.clone(),
+            Some(self.clone()),
+        ))
+    }
+
+    /// Frees every idle chunk which isn't held by an arena.
+    pub fn trim(&self) {
+        let mut state = self.0.state.lock().unwrap();
+        let before = state.retired.len();
+        state.retired.retain(|chunk| !chunk.is_free());
+        state.chunks -= before - state.retired.len();
+        self.coalesce_releases(&state, None);
+    }
+
+    /// Counts a new dedicated chunk of one of the arenas, failing if the pool is at its chunk
+    /// limit.
+    pub(crate) fn reserve_dedicated(&self) -> Result<(), AllocError> {
+        let state = self.0.state.lock().unwrap();
+        if !self.has_room(&state) {
+            return Err(AllocError::Exhausted);
+        }
+
+        self.0.dedicated.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Whether another chunk can be allocated without exceeding the chunk limit.
+    fn has_room(&self, state: &State<T>) -> bool {
+        state.chunks + self.0.dedicated.load(Ordering::Acquire) < state.max_chunks
+    }
+
+    /// Takes a free chunk out of the pool, allocating one if none is free. The chunk becomes the
+    /// front chunk of an arena.
+    fn take_free(&self, state: &mut State<T>) -> Result<Chunk<T>, AllocError> {
+        let chunk = if let Some(index) = state.retired.iter().position(|chunk| chunk.is_free()) {
+            let chunk = state.retired.swap_remove(index);
+            self.coalesce_releases(state, Some(chunk.id()));
+            chunk
+        } else if self.has_room(state) {
+            let layout = RingArena::<T>::chunk_layout(self.0.chunk_length, align_of::<T>());
+            let chunk =
+                Chunk::with_layout(layout, &self.0.source, Some(self.0.release_tx.clone()))?;
+            state.chunks += 1;
+            chunk
+        } else {
+            return Err(AllocError::Exhausted);
+        };
+
+        state.fronts.push(chunk.id());
+        Ok(chunk)
+    }
+
+    /// Drops the pending release messages which can't be of use to anyone: those of `reused`,
+    /// which is about to be allocated from again, those of chunks the pool no longer holds, and
+    /// duplicates. The others are sent again, so that the arenas waiting for them still wake up.
+    ///
+    /// Front chunks are released over and over without ever being retired, so this keeps the
+    /// channel from growing past the number of chunks of the pool.
+    fn coalesce_releases(&self, state: &State<T>, reused: Option<ChunkId>) {
+        let mut ids = Vec::new();
+        let mut gone = None;
+        for id in self.0.release_rx.drain() {
+            if Some(id) == reused {
+                continue;
+            } else if state.fronts.contains(&id) || state.retired.iter().any(|c| c.id() == id) {
+                ids.push(id);
+            } else {
+                gone = Some(id);
+            }
+        }
+
+        ids.sort_unstable();
+        ids.dedup();
+
+        // released dedicated chunks and trimmed chunks make room for a new chunk, so keep one
+        // of their messages to wake up an arena while there's room
+        if let Some(id) = gone
+            && self.has_room(state)
+        {
+            ids.push(id);
+        }
+
+        for id in ids {
+            let _ = self.0.release_tx.send(id);
+        }
+    }
+
+    /// Replaces the full front chunk of an arena with a free chunk. The front chunk is kept if
+    /// it's free already.
+    pub(crate) fn rotate(&self, front: &mut Chunk<T>) -> Result<(), AllocError> {
+        let mut state = self.0.state.lock().unwrap();
+        if front.is_free() {
+            self.coalesce_releases(&state, Some(front.id()));
+            return Ok(());
+        }
+
+        let next = self.take_free(&mut state)?;
+        let full = mem::replace(front, next);
+        Self::unhold(&mut state, &full);
+        state.retired.push(full);
+
+        Ok(())
+    }
+
+    /// Notes that `chunk` is no longer the front chunk of an arena.
+    fn unhold(state: &mut State<T>, chunk: &Chunk<T>) {
+        if let Some(index) = state.fronts.iter().position(|&id| id == chunk.id()) {
+            state.fronts.swap_remove(index);
+        }
+    }
+
+    /// Returns the front chunk of a dropped arena to the pool.
+    pub(crate) fn retire(&self, chunk: Chunk<T>) {
+        let id = chunk.is_free().then(|| chunk.id());
+        let mut state = self.0.state.lock().unwrap();
+        Self::unhold(&mut state, &chunk);
+        state.retired.push(chunk);
+        drop(state);
+
+        if let Some(id) = id {
+            let _ = self.0.release_tx.send(id);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{AllocError, Handle, HandleInner, RingArena, RingArenaStats};
+use std::{alloc::Layout, num::NonZero, ptr::NonNull};
+
+/// Alignment of the start of each chunk of a [`RawRingArena`]. Allocations with a larger alignment
+/// are padded as needed.
+const CHUNK_ALIGN: usize = 16;
+
+/// Arena for short-lived objects of any type, which hands out chunk space for arbitrary
+/// [`Layout`]s.
+///
+/// Allocations which don't fit in a chunk get a dedicated chunk of their own.
+pub struct RawRingArena(RingArena<u8>);
+
+impl RawRingArena {
+    /// Creates an arena whose chunks are `chunk_size` bytes long.
+    pub fn new(chunk_size: NonZero<usize>) -> Self {
+        Self(RingArena::with_alignment(chunk_size, CHUNK_ALIGN))
+    }
+
+    /// Limits the number of chunks this arena may allocate, including dedicated chunks. `None`
+    /// removes the limit.
+    ///
+    /// See [`RingArena::set_max_chunks`].
+    pub fn set_max_chunks(&mut self, max_chunks: Option<NonZero<usize>>) {
+        self.0.set_max_chunks(max_chunks);
+    }
+
+    /// Limits the memory used by the chunks of this arena to roughly `max_bytes`.
+    ///
+    /// See [`RingArena::set_max_bytes`].
+    pub fn set_max_bytes(&mut self, max_bytes: Option<NonZero<usize>>) {
+        self.0.set_max_bytes(max_bytes);
+    }
+
+    /// Returns statistics about this arena.
+    pub fn stats(&self) -> RingArenaStats {
+        self.0.stats()
+    }
+
+    /// Frees every idle chunk except the front one.
+    pub fn trim(&mut self) {
+        self.0.trim();
+    }
+
+    /// Allocates `layout.size()` bytes aligned to `layout.align()`.
+    ///
+    /// # Panics
+    /// Panics if [`RawRingArena::try_allocate`] fails.
+    pub fn allocate(&mut self, layout: Layout) -> Handle<u8> {
+        match self.try_allocate(layout) {
+            Ok(handle) => handle,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Allocates `layout.size()` bytes aligned to `layout.align()`, failing if the front chunk is
+    /// full and the arena can't allocate another one because of its chunk limit.
+    pub fn try_allocate(&mut self, layout: Layout) -> Result<Handle<u8>, AllocError> {
+        let arena = &mut self.0;
+        let (size, align) = (layout.size(), layout.align());
+        if size == 0 {
+            // nothing is reserved, and no chunk is held, but the pointer must still be aligned for
+            // slices of zero-sized elements
+            let ptr = NonNull::without_provenance(NonZero::new(align).unwrap());
+            arena.counters.allocations += 1;
+            return Ok(Handle(HandleInner::Dangling(
+                NonNull::slice_from_raw_parts(ptr, 0),
+            )));
+        }
+
+        // chunks are aligned to `arena.align`, so this is the most padding a fresh chunk needs
+        let max_padding = align.saturating_sub(arena.align);
+        if size + max_padding > arena.chunk_length {
+            return arena.allocate_dedicated(size, align.max(arena.align));
+        }
+
+        let mut padding = front_padding(arena, align);
+        if arena.chunk_length - arena.offset < padding + size {
+            arena.rotate()?;
+            padding = front_padding(arena, align);
+        }
+
+        arena.offset += padding;
+        // SAFETY: the padded allocation fits in the front chunk, as checked above
+        unsafe { Ok(arena.allocate_unchecked(size)) }
+    }
+
+    /// Allocates a `U`.
+    ///
+    /// # Panics
+    /// Panics if [`RawRingArena::try_allocate`] fails.
+    pub fn alloc<U>(&mut self) -> Handle<U> {
+        self.alloc_slice(1)
+    }
+
+    /// Allocates a `[U]` of the given length.
+    ///
+    /// # Panics
+    /// Panics if [`RawRingArena::try_alloc_slice`] fails.
+    pub fn alloc_slice<U>(&mut self, length: usize) -> Handle<U> {
+        match self.try_alloc_slice(length) {
+            Ok(handle) => handle,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Allocates a `[U]` of the given length, failing if [`RawRingArena::try_allocate`] does or
+    /// if the slice is too large for a [`Layout`].
+    pub fn try_alloc_slice<U>(&mut self, length: usize) -> Result<Handle<U>, AllocError> {
+        let layout = Layout::array::<U>(length).map_err(|_| AllocError::Oversized)?;
+        match self.try_allocate(layout)?.0 {
+            HandleInner::Chunk { ptr, _chunk } => Ok(Handle(HandleInner::Chunk {
+                ptr: NonNull::slice_from_raw_parts(ptr.cast(), length),
+                _chunk,
+            })),
+            // empty, or of zero-sized elements
+            HandleInner::Dangling(ptr) => Ok(Handle(HandleInner::Dangling(
+                NonNull::slice_from_raw_parts(ptr.cast(), length),
+            ))),
+            HandleInner::Boxed(_) => unreachable!("raw allocations are never boxed"),
+        }
+    }
+}
+
+/// Number of bytes to skip in the front chunk of `arena` so that its next allocation is aligned to
+/// `align`.
+fn front_padding(arena: &RingArena<u8>, align: usize) -> usize {
+    let front = arena.chunks.front().unwrap();
+    // SAFETY: the offset is within the bounds of (or one past) the chunk
+    let next = unsafe { front.region(arena.offset, 0) };
+    next.cast::<u8>().align_offset(align)
+}
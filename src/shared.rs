@@ -0,0 +1,123 @@
+use crate::{ChunkRef, HandleInner, InitHandle};
+use std::{
+    mem,
+    ops::{Deref, RangeBounds},
+    ptr::NonNull,
+};
+use triomphe::Arc;
+
+enum Owner<T> {
+    /// Elements which don't need to be dropped only need the chunk to be kept alive.
+    Chunk(ChunkRef),
+    /// Otherwise, the elements are dropped once the last clone is gone.
+    Shared(Arc<InitHandle<T>>),
+    Empty,
+}
+
+impl<T> Clone for Owner<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Owner::Chunk(chunk) => Owner::Chunk(chunk.clone()),
+            Owner::Shared(handle) => Owner::Shared(handle.clone()),
+            Owner::Empty => Owner::Empty,
+        }
+    }
+}
+
+/// Cloneable, read-only handle to an initialized `[T]` in a
+/// [`RingArena<T>`](crate::RingArena), see [`InitHandle::into_shared`].
+///
+/// Cloning only increments a reference count, and the chunk stays in use until every clone is
+/// dropped.
+pub struct SharedHandle<T> {
+    ptr: NonNull<[T]>,
+    owner: Owner<T>,
+}
+
+unsafe impl<T> Send for SharedHandle<T> where T: Send + Sync {}
+unsafe impl<T> Sync for SharedHandle<T> where T: Send + Sync {}
+
+impl<T> InitHandle<T> {
+    /// Converts this handle into a [`SharedHandle<T>`].
+    ///
+    /// For types which need to be dropped or for boxed handles, this allocates the reference count
+    /// shared by the clones. Otherwise, clones share the reference count of the chunk.
+    pub fn into_shared(self) -> SharedHandle<T> {
+        if mem::needs_drop::<T>() || self.is_boxed() {
+            let handle = Arc::new(self);
+            return SharedHandle {
+                ptr: NonNull::from_ref(handle.as_slice()),
+                owner: Owner::Shared(handle),
+            };
+        }
+
+        let ptr = NonNull::from_ref(self.as_slice());
+        let owner = match self.into_uninit().0 {
+            HandleInner::Chunk { _chunk, .. } => Owner::Chunk(_chunk),
+            HandleInner::Dangling(_) => Owner::Empty,
+            HandleInner::Boxed(_) => unreachable!("boxed handles are shared through an Arc"),
+        };
+
+        SharedHandle { ptr, owner }
+    }
+}
+
+impl<T> SharedHandle<T> {
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the owner keeps the elements alive and initialized, and nobody can mutate them
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Returns a handle to a subslice of this one, which keeps the chunk in use as a clone would.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> SharedHandle<T> {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        Self {
+            ptr: NonNull::from_ref(&self.as_slice()[range]),
+            owner: self.owner.clone(),
+        }
+    }
+}
+
+impl<T> Clone for SharedHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            ptr: self.ptr,
+            owner: self.owner.clone(),
+        }
+    }
+}
+
+impl<T> Deref for SharedHandle<T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> AsRef<[T]> for SharedHandle<T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> From<InitHandle<T>> for SharedHandle<T> {
+    fn from(handle: InitHandle<T>) -> Self {
+        handle.into_shared()
+    }
+}
+
+/// Converts the handle into [`bytes::Bytes`] without copying. The chunk is held until the last
+/// clone of either is dropped.
+#[cfg(feature = "bytes")]
+impl From<SharedHandle<u8>> for bytes::Bytes {
+    fn from(handle: SharedHandle<u8>) -> Self {
+        bytes::Bytes::from_owner(handle)
+    }
+}
@@ -0,0 +1,46 @@
+use std::{
+    alloc::{self, Layout},
+    ptr::NonNull,
+};
+
+/// Source of the memory backing the chunks of a [`RingArena`](crate::RingArena), see
+/// [`RingArena::with_source`](crate::RingArena::with_source).
+///
+/// Chunks are given back to their source once the arena and every handle into them are gone,
+/// possibly on another thread.
+///
+/// # Safety
+/// Memory returned by [`ChunkSource::allocate`] must be valid for reads and writes of
+/// `layout.size()` bytes, aligned to `layout.align()`, and must not be used by anything else until
+/// it's given back through [`ChunkSource::deallocate`].
+pub unsafe trait ChunkSource: Send + Sync {
+    /// Allocates memory for a chunk with the given layout, which has a non-zero size. Returns
+    /// `None` if no memory is available, which fails the allocation with
+    /// [`AllocError::Exhausted`](crate::AllocError::Exhausted).
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Gives back the memory of a freed chunk.
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by [`ChunkSource::allocate`] with the same `layout`, and not
+    /// been given back already.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// Allocates chunks from the global allocator. Running out of memory aborts, as with any other
+/// allocation, see [`alloc::handle_alloc_error`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlobalSource;
+
+unsafe impl ChunkSource for GlobalSource {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // SAFETY: layout has a non-zero size, as stabilished by the method contract
+        let ptr = unsafe { alloc::alloc(layout) };
+        Some(NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout)))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: ptr was allocated with this layout, as stabilished by the method contract
+        unsafe { alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+}
@@ -0,0 +1,13 @@
+//! Synchronization primitives used by chunks and the concurrent arena, which are swapped for
+//! loom's when model checking with `--cfg loom`.
+
+#[cfg(loom)]
+pub(crate) use loom::sync::{
+    Mutex,
+    atomic::{AtomicPtr, AtomicUsize, Ordering, fence},
+};
+#[cfg(not(loom))]
+pub(crate) use std::sync::{
+    Mutex,
+    atomic::{AtomicPtr, AtomicUsize, Ordering, fence},
+};
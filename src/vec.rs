@@ -0,0 +1,178 @@
+use crate::{AllocError, Handle, HandleInner, InitHandle, RingArena};
+use std::{
+    mem,
+    ops::{Deref, DerefMut},
+};
+
+/// Growable `[T]` in a [`RingArena<T>`], see [`RingArena::vec`].
+///
+/// Elements are pushed into a region of the arena, which grows in place while it's the last
+/// allocation in the front chunk. Otherwise, they're moved to a new region large enough for them.
+pub struct ArenaVec<'a, T> {
+    arena: &'a mut RingArena<T>,
+    /// Region the elements are pushed into.
+    handle: Handle<T>,
+    /// Number of initialized elements.
+    length: usize,
+}
+
+impl<T> RingArena<T> {
+    /// Creates an empty vector which allocates from this arena.
+    pub fn vec(&mut self) -> ArenaVec<'_, T> {
+        ArenaVec {
+            arena: self,
+            handle: Handle(HandleInner::empty()),
+            length: 0,
+        }
+    }
+}
+
+impl<T> ArenaVec<'_, T> {
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Number of elements the vector can hold without growing.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.handle.as_slice().len()
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `length` elements are initialized
+        unsafe { self.handle.as_slice()[..self.length].assume_init_ref() }
+    }
+
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `length` elements are initialized
+        unsafe { self.handle.as_mut_slice()[..self.length].assume_init_mut() }
+    }
+
+    /// Makes room for `additional` more elements, failing if the arena can't provide a region
+    /// large enough for them.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        let capacity = self.capacity();
+        let needed = self.length + additional;
+        if needed <= capacity {
+            return Ok(());
+        }
+
+        // grow geometrically, but don't exceed a chunk unless we have to
+        let mut new_capacity = needed.max(capacity * 2).max(4);
+        if needed <= self.arena.chunk_length {
+            new_capacity = new_capacity.min(self.arena.chunk_length);
+        }
+
+        // only the initialized elements are moved, and the old region is simply abandoned
+        self.arena
+            .try_grow_prefix(&mut self.handle, new_capacity, self.length)
+    }
+
+    /// Makes room for `additional` more elements.
+    ///
+    /// # Panics
+    /// Panics if [`ArenaVec::try_reserve`] fails.
+    pub fn reserve(&mut self, additional: usize) {
+        if let Err(e) = self.try_reserve(additional) {
+            panic!("{e}");
+        }
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// # Panics
+    /// Panics if [`ArenaVec::try_reserve`] fails.
+    pub fn push(&mut self, value: T) {
+        self.reserve(1);
+        self.handle.as_mut_slice()[self.length].write(value);
+        self.length += 1;
+    }
+
+    /// Removes the last element and returns it, or `None` if the vector is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+        // SAFETY: the element was initialized, and it's no longer considered so
+        Some(unsafe { self.handle.as_slice()[self.length].assume_init_read() })
+    }
+
+    /// Shortens the vector to its first `length` elements, dropping the rest. Does nothing if
+    /// `length` is not smaller than the current length.
+    pub fn truncate(&mut self, length: usize) {
+        if length >= self.length {
+            return;
+        }
+
+        let old_length = mem::replace(&mut self.length, length);
+        // SAFETY: the elements were initialized, and they're no longer considered so
+        unsafe { self.handle.as_mut_slice()[length..old_length].assume_init_drop() };
+    }
+
+    /// Drops every element.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Finishes the vector, returning its elements. Unused space at the end of the region is
+    /// given back to the arena.
+    pub fn finish(mut self) -> InitHandle<T> {
+        let mut handle = mem::replace(&mut self.handle, Handle(HandleInner::empty()));
+        let length = mem::take(&mut self.length);
+        self.arena.shrink(&mut handle, length);
+
+        // SAFETY: the handle has been shrunk to the initialized elements
+        unsafe { handle.assume_init() }
+    }
+}
+
+impl<T> Extend<T> for ArenaVec<'_, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|value| self.push(value));
+    }
+}
+
+impl<'b, T> Extend<&'b T> for ArenaVec<'_, T>
+where
+    T: Copy + 'b,
+{
+    fn extend<I: IntoIterator<Item = &'b T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> Deref for ArenaVec<'_, T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> DerefMut for ArenaVec<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> Drop for ArenaVec<'_, T> {
+    fn drop(&mut self) {
+        self.clear();
+        // give back the space if the vector wasn't finished
+        self.arena.shrink(&mut self.handle, 0);
+    }
+}
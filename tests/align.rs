@@ -0,0 +1,66 @@
+use ring_arena::{Handle, OversizePolicy, RingArena};
+use std::num::NonZero;
+
+fn addr<T>(handle: &Handle<T>) -> usize {
+    handle.as_slice().as_ptr().addr()
+}
+
+#[test]
+fn chunks_start_at_the_requested_alignment() {
+    for align in [64, 4096] {
+        let mut arena = RingArena::<u8>::with_alignment(NonZero::new(100).unwrap(), align);
+        arena.set_oversize_policy(OversizePolicy::Dedicated);
+
+        let first = arena.allocate(99);
+        assert_eq!(addr(&first) % align, 0);
+
+        // the first chunk is full, so this one starts a new chunk
+        let second = arena.allocate(2);
+        assert_eq!(arena.stats().chunks, 2);
+        assert_eq!(addr(&second) % align, 0);
+
+        let dedicated = arena.allocate(1000);
+        assert_eq!(arena.stats().dedicated_chunks, 1);
+        assert_eq!(addr(&dedicated) % align, 0);
+    }
+}
+
+#[test]
+fn aligned_allocations_are_padded_to_the_chunk_alignment() {
+    let mut arena = RingArena::<u32>::with_alignment(NonZero::new(64).unwrap(), 64);
+    arena.set_align_allocations(true);
+
+    let first = arena.allocate(1);
+    let second = arena.allocate(16);
+    // 64 bytes are 16 elements of 4 bytes
+    assert_eq!(addr(&second), addr(&first) + 64);
+    assert_eq!(arena.stats().front_offset, 32);
+
+    // the second allocation ends on the alignment, so no padding is needed after it
+    let third = arena.allocate(1);
+    assert_eq!(addr(&third), addr(&first) + 128);
+    assert_eq!(arena.stats().front_offset, 33);
+}
+
+#[test]
+fn aligned_allocations_of_odd_sized_elements_are_padded_to_a_common_multiple() {
+    // 3 and 64 share no factor, so allocations start 64 elements apart
+    let mut arena = RingArena::<[u8; 3]>::with_alignment(NonZero::new(256).unwrap(), 64);
+    arena.set_align_allocations(true);
+
+    let first = arena.allocate(1);
+    let second = arena.allocate(1);
+    assert_eq!(addr(&second) % 64, 0);
+    assert_eq!(addr(&second), addr(&first) + 64 * 3);
+    assert_eq!(arena.stats().front_offset, 65);
+
+    // 6 and 64 share a factor of 2, so allocations start 32 elements apart
+    let mut arena = RingArena::<[u8; 6]>::with_alignment(NonZero::new(256).unwrap(), 64);
+    arena.set_align_allocations(true);
+
+    let first = arena.allocate(1);
+    let second = arena.allocate(1);
+    assert_eq!(addr(&second) % 64, 0);
+    assert_eq!(addr(&second), addr(&first) + 32 * 6);
+    assert_eq!(arena.stats().front_offset, 33);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "async")]
+
+use ring_arena::RingArena;
+use std::{
+    future::Future,
+    num::NonZero,
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+/// Wakes the thread blocked on a future, counting how many times it did.
+struct ThreadWaker {
+    thread: Thread,
+    wakes: AtomicUsize,
+}
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wakes.fetch_add(1, Ordering::Relaxed);
+        self.thread.unpark();
+    }
+}
+
+/// Minimal executor: polls the future on the current thread, parking it until woken.
+fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+    let waker = Arc::new(ThreadWaker {
+        thread: thread::current(),
+        wakes: AtomicUsize::new(0),
+    });
+    let cx_waker = Waker::from(waker.clone());
+    let mut cx = Context::from_waker(&cx_waker);
+
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return (output, waker.wakes.load(Ordering::Relaxed));
+        }
+
+        thread::park();
+    }
+}
+
+#[test]
+fn allocate_async_returns_immediately_when_not_exhausted() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(4).unwrap());
+    let (handle, wakes) = block_on(arena.allocate_async(4));
+
+    assert_eq!(handle.as_slice().len(), 4);
+    assert_eq!(wakes, 0);
+}
+
+#[test]
+fn allocate_async_wakes_when_a_handle_is_dropped_on_another_thread() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(4).unwrap());
+    arena.set_max_chunks(NonZero::new(1));
+
+    let busy = arena.allocate(4);
+    let busy_ptr = busy.as_slice().as_ptr();
+    let dropper = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        drop(busy);
+    });
+
+    let (handle, wakes) = block_on(arena.allocate_async(4));
+    dropper.join().unwrap();
+
+    // the only chunk was recycled
+    assert_eq!(handle.as_slice().as_ptr(), busy_ptr);
+    assert!(wakes >= 1);
+}
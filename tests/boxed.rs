@@ -0,0 +1,43 @@
+mod common;
+
+use common::Tracked;
+use ring_arena::RingArena;
+use std::{cell::Cell, num::NonZero, rc::Rc};
+
+#[test]
+fn alloc_one_moves_the_value_into_the_front_chunk() {
+    let mut arena = RingArena::new(NonZero::new(16).unwrap());
+    let _first = arena.allocate(2);
+
+    let mut value = arena.alloc_one(7u32);
+    *value += 1;
+    assert_eq!(*value, 8);
+    assert!(!value.into_handle().is_boxed());
+    assert_eq!(arena.stats().front_offset, 3);
+}
+
+#[test]
+fn into_inner_moves_the_value_out_without_dropping_it() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = RingArena::new(NonZero::new(16).unwrap());
+
+    let value = arena.alloc_one(Tracked::new(&drops)).into_inner();
+    assert_eq!(drops.get(), 0);
+    assert_eq!(arena.stats().busy_chunks, 0);
+
+    drop(value);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn dropping_the_box_drops_the_value_once() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = RingArena::new(NonZero::new(16).unwrap());
+
+    let value = arena.alloc_one(Tracked::new(&drops));
+    assert_eq!(arena.stats().busy_chunks, 1);
+
+    drop(value);
+    assert_eq!(drops.get(), 1);
+    assert_eq!(arena.stats().busy_chunks, 0);
+}
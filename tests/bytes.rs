@@ -0,0 +1,49 @@
+#![cfg(feature = "bytes")]
+
+use bytes::Bytes;
+use ring_arena::RingArena;
+use std::{io::Write, num::NonZero};
+
+#[test]
+fn bytes_keep_the_chunk_until_the_last_clone_drops() {
+    let mut arena = RingArena::new(NonZero::new(16).unwrap());
+    let handle = arena.allocate_clone(b"header payload");
+    let start = handle.as_ptr();
+
+    let bytes = Bytes::from(handle);
+    assert_eq!(bytes.as_ptr(), start);
+    let header = bytes.slice(..6);
+    let payload = bytes.slice(7..);
+
+    drop(bytes);
+    drop(header);
+    assert_eq!(&payload[..], b"payload");
+    assert_eq!(arena.stats().busy_chunks, 1);
+
+    drop(payload);
+    assert_eq!(arena.stats().busy_chunks, 0);
+}
+
+#[test]
+fn shared_handles_and_frozen_bytes_convert_without_copying() {
+    let mut arena = RingArena::new(NonZero::new(16).unwrap());
+    let shared = arena.allocate_clone(b"abc").into_shared();
+    let clone = Bytes::from(shared.clone());
+    assert_eq!(clone.as_ptr(), shared.as_ptr());
+
+    drop(shared);
+    assert_eq!(arena.stats().busy_chunks, 1);
+    drop(clone);
+    assert_eq!(arena.stats().busy_chunks, 0);
+
+    let mut writer = arena.writer();
+    writer.write_all(b"written").unwrap();
+    let frozen = writer.freeze();
+    let start = frozen.as_ptr();
+
+    let bytes = Bytes::from(frozen);
+    assert_eq!(bytes.as_ptr(), start);
+    assert_eq!(arena.stats().busy_chunks, 1);
+    drop(bytes);
+    assert_eq!(arena.stats().busy_chunks, 0);
+}
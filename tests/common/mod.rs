@@ -0,0 +1,93 @@
+//! Fixtures shared by the integration tests. Each test crate only uses some of them.
+#![allow(dead_code)]
+
+use ring_arena::{ChunkSource, GlobalSource};
+use std::{
+    alloc::Layout,
+    cell::Cell,
+    ptr::NonNull,
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// Counts how many times it's dropped, and panics when cloned if `explode` is set.
+pub struct Tracked {
+    pub drops: Rc<Cell<usize>>,
+    pub explode: bool,
+}
+
+impl Tracked {
+    pub fn new(drops: &Rc<Cell<usize>>) -> Self {
+        Self {
+            drops: drops.clone(),
+            explode: false,
+        }
+    }
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        assert!(!self.explode, "boom");
+        Self::new(&self.drops)
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+struct Counting {
+    limit: usize,
+    allocated: AtomicUsize,
+    freed: AtomicUsize,
+}
+
+/// Global allocator which gives out at most `limit` chunks at once, counting the chunks it gives
+/// out and takes back.
+#[derive(Clone)]
+pub struct CountingSource(Arc<Counting>);
+
+impl CountingSource {
+    pub fn with_limit(limit: usize) -> Self {
+        Self(Arc::new(Counting {
+            limit,
+            allocated: AtomicUsize::new(0),
+            freed: AtomicUsize::new(0),
+        }))
+    }
+
+    pub fn allocated(&self) -> usize {
+        self.0.allocated.load(Ordering::Relaxed)
+    }
+
+    pub fn freed(&self) -> usize {
+        self.0.freed.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for CountingSource {
+    fn default() -> Self {
+        Self::with_limit(usize::MAX)
+    }
+}
+
+unsafe impl ChunkSource for CountingSource {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if self.allocated() - self.freed() == self.0.limit {
+            return None;
+        }
+
+        self.0.allocated.fetch_add(1, Ordering::Relaxed);
+        GlobalSource.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.freed.fetch_add(1, Ordering::Relaxed);
+        unsafe { GlobalSource.deallocate(ptr, layout) };
+    }
+}
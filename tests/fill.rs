@@ -0,0 +1,122 @@
+mod common;
+
+use common::Tracked;
+use ring_arena::RingArena;
+use std::{
+    cell::Cell,
+    num::NonZero,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+};
+
+/// Claims to have `len` elements, but only yields `actual`.
+struct ShortIter<'a> {
+    drops: &'a Rc<Cell<usize>>,
+    len: usize,
+    actual: usize,
+}
+
+impl Iterator for ShortIter<'_> {
+    type Item = Tracked;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.actual > 0).then(|| {
+            self.actual -= 1;
+            self.len -= 1;
+            Tracked::new(self.drops)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl ExactSizeIterator for ShortIter<'_> {}
+
+fn arena() -> RingArena<Tracked> {
+    RingArena::new(NonZero::new(16).unwrap())
+}
+
+#[test]
+fn allocate_with_initializes_every_element() {
+    let mut arena = RingArena::new(NonZero::new(16).unwrap());
+    let handle = arena.allocate_with(5, |i| i * 2);
+    assert_eq!(*handle, [0, 2, 4, 6, 8]);
+}
+
+#[test]
+fn allocate_with_drops_initialized_prefix_on_panic() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = arena();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        arena.allocate_with(8, |i| {
+            assert!(i < 3, "boom");
+            Tracked::new(&drops)
+        })
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn allocate_clone_drops_initialized_prefix_on_panic() {
+    let drops = Rc::new(Cell::new(0));
+    let mut values: Vec<_> = (0..6).map(|_| Tracked::new(&drops)).collect();
+    values[4].explode = true;
+    let mut arena = arena();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| arena.allocate_clone(&values)));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 4);
+    drop(values);
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn allocate_from_exact_iter_drops_initialized_prefix_on_panic() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = arena();
+
+    let iter = (0..5).map(|i| {
+        assert!(i < 2, "boom");
+        Tracked::new(&drops)
+    });
+    let result = panic::catch_unwind(AssertUnwindSafe(|| arena.allocate_from_exact_iter(iter)));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn allocate_from_exact_iter_panics_on_short_iterator() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = arena();
+
+    let iter = ShortIter {
+        drops: &drops,
+        len: 5,
+        actual: 3,
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| arena.allocate_from_exact_iter(iter)));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn completed_handles_drop_their_elements() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = arena();
+
+    let values: Vec<_> = (0..3).map(|_| Tracked::new(&drops)).collect();
+    let handle = arena.allocate_clone(&values);
+    drop(values);
+    assert_eq!(drops.get(), 3);
+
+    drop(handle);
+    assert_eq!(drops.get(), 6);
+}
@@ -0,0 +1,77 @@
+use ring_arena::{Handle, RingArena};
+use std::num::NonZero;
+
+fn addr(handle: &Handle<u32>) -> usize {
+    handle.as_slice().as_ptr().addr()
+}
+
+#[test]
+fn grow_in_place_moves_the_front_offset() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(16).unwrap());
+    let mut handle = arena.allocate(4);
+    let start = addr(&handle);
+
+    assert!(arena.grow_in_place(&mut handle, 10));
+    assert_eq!(addr(&handle), start);
+    assert_eq!(handle.as_slice().len(), 10);
+    assert_eq!(arena.stats().front_offset, 10);
+
+    // the next allocation starts right after the grown one
+    let next = arena.allocate(2);
+    assert_eq!(addr(&next), start + 10 * size_of::<u32>());
+}
+
+#[test]
+fn grow_in_place_fails_unless_last_with_enough_room() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(16).unwrap());
+    let mut first = arena.allocate(4);
+    let mut last = arena.allocate(4);
+
+    assert!(!arena.grow_in_place(&mut first, 6));
+    assert!(!arena.grow_in_place(&mut last, 13));
+    assert_eq!(first.as_slice().len(), 4);
+    assert_eq!(last.as_slice().len(), 4);
+    assert_eq!(arena.stats().front_offset, 8);
+
+    assert!(arena.grow_in_place(&mut last, 12));
+    assert_eq!(arena.stats().front_offset, 16);
+}
+
+#[test]
+fn try_grow_moves_the_elements_when_not_last() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(16).unwrap());
+    let mut handle = arena.allocate(4);
+    for (i, element) in handle.as_mut_slice().iter_mut().enumerate() {
+        element.write(i as u32);
+    }
+    let start = addr(&handle);
+    let _other = arena.allocate(1);
+
+    arena.try_grow(&mut handle, 8).unwrap();
+    assert_ne!(addr(&handle), start);
+    assert_eq!(handle.as_slice().len(), 8);
+    assert_eq!(arena.stats().front_offset, 13);
+    for (i, element) in handle.as_slice()[..4].iter().enumerate() {
+        // SAFETY: the first 4 elements were initialized before growing
+        assert_eq!(unsafe { element.assume_init() }, i as u32);
+    }
+}
+
+#[test]
+fn shrink_gives_space_back_only_if_last() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(16).unwrap());
+    let mut first = arena.allocate(4);
+    let mut last = arena.allocate(8);
+    let start = addr(&last);
+
+    arena.shrink(&mut first, 2);
+    assert_eq!(first.as_slice().len(), 2);
+    assert_eq!(arena.stats().front_offset, 12);
+
+    arena.shrink(&mut last, 3);
+    assert_eq!(last.as_slice().len(), 3);
+    assert_eq!(arena.stats().front_offset, 7);
+
+    let next = arena.allocate(1);
+    assert_eq!(addr(&next), start + 3 * size_of::<u32>());
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{CountingSource, Tracked};
+use ring_arena::{AllocError, RingArena};
+use std::{cell::Cell, num::NonZero, rc::Rc, thread};
+
+#[test]
+fn handles_keep_their_chunk_alive_after_the_arena_is_dropped() {
+    let source = CountingSource::default();
+    let mut arena =
+        RingArena::<u32>::with_source(NonZero::new(16).unwrap(), 1, source.clone()).unwrap();
+    let mut handle = arena.allocate_with(4, |i| i as u32);
+    drop(arena);
+    assert_eq!(source.freed(), 0);
+
+    for element in handle.as_mut_slice() {
+        *element *= 10;
+    }
+    assert_eq!(*handle, [0, 10, 20, 30]);
+
+    // the chunk is freed along with its last handle, wherever it's dropped
+    thread::spawn(move || drop(handle)).join().unwrap();
+    assert_eq!(source.freed(), 1);
+}
+
+#[test]
+fn split_halves_keep_the_chunk_until_both_are_gone() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(4).unwrap());
+    arena.set_max_chunks(NonZero::new(1));
+    let handle = arena.allocate(4);
+    let start = handle.as_slice().as_ptr();
+
+    let (header, payload) = handle.split_at(1);
+    assert_eq!((header.as_slice().len(), payload.as_slice().len()), (1, 3));
+    assert_eq!(payload.as_slice().as_ptr(), start.wrapping_add(1));
+
+    drop(header);
+    assert_eq!(arena.try_allocate(1).err(), Some(AllocError::Exhausted));
+
+    drop(payload);
+    assert!(arena.try_allocate(1).is_ok());
+}
+
+#[test]
+fn split_initialized_halves_drop_their_own_elements() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = RingArena::new(NonZero::new(8).unwrap());
+    arena.set_max_chunks(NonZero::new(1));
+    let handle = arena.allocate_with(8, |_| Tracked::new(&drops));
+
+    let (header, payload) = handle.split_at(3);
+    drop(header);
+    assert_eq!(drops.get(), 3);
+    assert_eq!(arena.try_allocate(1).err(), Some(AllocError::Exhausted));
+
+    drop(payload);
+    assert_eq!(drops.get(), 8);
+    assert!(arena.try_allocate(1).is_ok());
+}
+
+#[test]
+fn empty_allocations_are_not_boxed_and_dont_use_a_chunk() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(4).unwrap());
+    let empty = arena.allocate(0);
+    assert!(!empty.is_boxed());
+    assert!(empty.as_slice().is_empty());
+
+    let stats = arena.stats();
+    assert_eq!(stats.busy_chunks, 0);
+    assert_eq!(stats.front_offset, 0);
+    assert_eq!(stats.allocations, 1);
+}
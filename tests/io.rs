@@ -0,0 +1,82 @@
+use ring_arena::RingArena;
+use std::{
+    io::{BufRead, Read, Write},
+    num::NonZero,
+};
+
+fn arena() -> RingArena<u8> {
+    RingArena::new(NonZero::new(16).unwrap())
+}
+
+#[test]
+fn writes_move_to_a_new_chunk_once_the_front_is_full() {
+    let mut arena = arena();
+    let _busy = arena.allocate(10);
+
+    let mut writer = arena.writer();
+    writer.write_all(b"0123").unwrap();
+    // only 6 bytes are left in the first chunk, so the written bytes move to a fresh one
+    writer.write_all(b"456789").unwrap();
+    writer.write_all(b"ab").unwrap();
+    assert_eq!(writer.as_slice(), b"0123456789ab");
+
+    let bytes = writer.freeze();
+    assert_eq!(&*bytes, b"0123456789ab");
+    let stats = arena.stats();
+    assert_eq!(stats.chunks, 2);
+    assert_eq!(stats.front_offset, 12);
+}
+
+#[test]
+fn freeze_gives_back_the_unused_space() {
+    let mut arena = arena();
+    let mut writer = arena.writer();
+    writer.write_all(b"hello").unwrap();
+
+    let bytes = writer.freeze();
+    assert_eq!(arena.stats().front_offset, 5);
+
+    // the next allocation starts right after the written bytes
+    let next = arena.allocate(1);
+    assert_eq!(next.as_slice().as_ptr().addr(), bytes.as_ptr().addr() + 5);
+}
+
+#[test]
+fn reads_advance_the_cursor_but_not_the_slice() {
+    let mut arena = arena();
+    let mut writer = arena.writer();
+    writer.write_all(b"hello\nworld\n").unwrap();
+    let mut bytes = writer.freeze();
+
+    let mut line = String::new();
+    bytes.read_line(&mut line).unwrap();
+    assert_eq!(line, "hello\n");
+    assert_eq!(bytes.position(), 6);
+
+    assert_eq!(bytes.fill_buf().unwrap(), b"world\n");
+    bytes.consume(2);
+    let mut rest = Vec::new();
+    bytes.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"rld\n");
+    assert_eq!(bytes.position(), 12);
+
+    // the slice always covers all of the bytes
+    assert_eq!(&*bytes, b"hello\nworld\n");
+    bytes.set_position(6);
+    let mut word = [0; 5];
+    bytes.read_exact(&mut word).unwrap();
+    assert_eq!(&word, b"world");
+}
+
+#[test]
+fn dropping_an_unfrozen_writer_gives_back_its_space() {
+    let mut arena = arena();
+    let _first = arena.allocate(2);
+
+    let mut writer = arena.writer();
+    writer.write_all(b"abc").unwrap();
+    drop(writer);
+
+    assert_eq!(arena.stats().front_offset, 2);
+    assert_eq!(arena.stats().busy_chunks, 1);
+}
@@ -0,0 +1,79 @@
+//! Model checks of [`ConcurrentRingArena`], run with:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --test loom
+//! ```
+
+#![cfg(loom)]
+
+use loom::{sync::Arc, thread};
+use ring_arena::{ConcurrentRingArena, Handle};
+use std::{num::NonZero, ops::Range};
+
+fn range<T>(handle: &Handle<T>) -> Range<usize> {
+    let start = handle.as_slice().as_ptr().addr();
+    start..start + size_of_val(handle.as_slice())
+}
+
+fn assert_disjoint(a: &Handle<u32>, b: &Handle<u32>) {
+    let (a, b) = (range(a), range(b));
+    assert!(a.end <= b.start || b.end <= a.start, "{a:?} overlaps {b:?}");
+}
+
+/// Fills the handle with `value`, so overlapping handles are caught by `check`.
+fn fill(mut handle: Handle<u32>, value: u32) -> Handle<u32> {
+    for element in handle.as_mut_slice() {
+        element.write(value);
+    }
+
+    handle
+}
+
+fn check(handle: &Handle<u32>, value: u32) {
+    for element in handle.as_slice() {
+        // SAFETY: the whole handle was filled
+        assert_eq!(unsafe { element.assume_init() }, value);
+    }
+}
+
+#[test]
+fn concurrent_bump_and_rotate() {
+    loom::model(|| {
+        let arena = Arc::new(ConcurrentRingArena::<u32>::new(NonZero::new(2).unwrap()));
+
+        // one of the allocations doesn't fit next to the other, so it rotates while the other
+        // might still be bumping
+        let other = arena.clone();
+        let small = thread::spawn(move || fill(other.allocate(1), 1));
+        let large = fill(arena.allocate(2), 2);
+        let small = small.join().unwrap();
+
+        assert_disjoint(&small, &large);
+        check(&small, 1);
+        check(&large, 2);
+    });
+}
+
+#[test]
+fn recycle_with_stale_front() {
+    loom::model(|| {
+        let arena = Arc::new(ConcurrentRingArena::<u32>::new(NonZero::new(1).unwrap()));
+        arena.set_max_chunks(NonZero::new(2));
+
+        let first = arena.allocate(1);
+
+        // releasing the only handle lets its chunk be recycled, while the other thread might
+        // still be looking at it as the front chunk
+        let other = arena.clone();
+        let recycler = thread::spawn(move || {
+            drop(first);
+            fill(other.allocate(1), 1)
+        });
+        let stale = fill(arena.try_allocate(1).unwrap(), 2);
+        let recycled = recycler.join().unwrap();
+
+        assert_disjoint(&recycled, &stale);
+        check(&recycled, 1);
+        check(&stale, 2);
+    });
+}
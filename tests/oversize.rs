@@ -0,0 +1,46 @@
+mod common;
+
+use common::CountingSource;
+use ring_arena::{AllocError, OversizePolicy, RingArena};
+use std::num::NonZero;
+
+#[test]
+fn dedicated_chunks_are_freed_with_their_last_handle() {
+    let source = CountingSource::default();
+    let mut arena =
+        RingArena::<u8>::with_source(NonZero::new(16).unwrap(), 1, source.clone()).unwrap();
+    arena.set_oversize_policy(OversizePolicy::Dedicated);
+
+    let handle = arena.allocate(1 << 20);
+    assert!(!handle.is_boxed());
+    assert_eq!(arena.stats().dedicated_chunks, 1);
+    assert_eq!(source.allocated(), 2);
+
+    drop(handle);
+    assert_eq!(source.freed(), 1);
+    assert_eq!(arena.stats().dedicated_chunks, 0);
+}
+
+#[test]
+fn dedicated_chunks_count_towards_the_chunk_limit_until_released() {
+    let mut arena = RingArena::<u8>::new(NonZero::new(16).unwrap());
+    arena.set_oversize_policy(OversizePolicy::Dedicated);
+    arena.set_max_chunks(NonZero::new(2));
+
+    let handle = arena.allocate(64);
+    assert_eq!(arena.try_allocate(64).err(), Some(AllocError::Exhausted));
+
+    drop(handle);
+    assert!(arena.try_allocate(64).is_ok());
+    assert_eq!(arena.stats().dedicated_allocations, 2);
+}
+
+#[test]
+fn oversize_policy_is_visible_through_handles_and_stats() {
+    let mut arena = RingArena::<u8>::new(NonZero::new(16).unwrap());
+    assert!(arena.allocate(64).is_boxed());
+    assert_eq!(arena.stats().boxed_allocations, 1);
+
+    arena.set_oversize_policy(OversizePolicy::Error);
+    assert_eq!(arena.try_allocate(64).err(), Some(AllocError::Oversized));
+}
@@ -0,0 +1,75 @@
+use ring_arena::{AllocError, OversizePolicy, SharedChunkPool};
+use std::{num::NonZero, thread, time::Duration};
+
+#[test]
+fn free_front_chunk_is_reused_in_place() {
+    let pool = SharedChunkPool::<u32>::new(NonZero::new(4).unwrap());
+    pool.set_max_chunks(NonZero::new(1));
+    let mut arena = pool.arena().unwrap();
+
+    let ptr = arena.allocate(4).as_slice().as_ptr();
+    for _ in 0..1000 {
+        assert_eq!(arena.allocate(4).as_slice().as_ptr(), ptr);
+    }
+}
+
+#[test]
+fn blocked_arena_wakes_while_others_reuse_their_front_chunk() {
+    let pool = SharedChunkPool::<u32>::new(NonZero::new(1).unwrap());
+    pool.set_max_chunks(NonZero::new(2));
+    let mut waiting = pool.arena().unwrap();
+    let mut busy = pool.arena().unwrap();
+
+    // both chunks are taken, so the next allocation of `waiting` blocks until `held` is dropped
+    let held = waiting.allocate(1);
+    let held_addr = held.as_slice().as_ptr().addr();
+    let waiter = thread::spawn(move || waiting.allocate_blocking(1).as_slice().as_ptr().addr());
+
+    thread::sleep(Duration::from_millis(50));
+    drop(held);
+
+    // the other arena rotating in place must not swallow the release of `held`
+    for _ in 0..1000 {
+        drop(busy.allocate(1));
+    }
+
+    assert_eq!(waiter.join().unwrap(), held_addr);
+}
+
+#[test]
+fn dedicated_chunks_count_towards_the_pool_limit() {
+    let pool = SharedChunkPool::<u32>::new(NonZero::new(4).unwrap());
+    pool.set_max_chunks(NonZero::new(2));
+    let mut arena = pool.arena().unwrap();
+    arena.set_oversize_policy(OversizePolicy::Dedicated);
+
+    let dedicated = arena.allocate(64);
+    assert_eq!(arena.try_allocate(64).err(), Some(AllocError::Exhausted));
+    assert_eq!(pool.arena().err(), Some(AllocError::Exhausted));
+    assert_eq!(arena.stats().dedicated_chunks, 1);
+
+    drop(dedicated);
+    assert_eq!(arena.stats().dedicated_chunks, 0);
+    assert!(arena.try_allocate(64).is_ok());
+}
+
+#[test]
+fn blocked_arena_wakes_when_a_dedicated_chunk_is_released() {
+    let pool = SharedChunkPool::<u32>::new(NonZero::new(1).unwrap());
+    pool.set_max_chunks(NonZero::new(2));
+    let mut arena = pool.arena().unwrap();
+    arena.set_oversize_policy(OversizePolicy::Dedicated);
+
+    // the dedicated chunk takes the room left for a second chunk
+    let dedicated = arena.allocate(4);
+    let held = arena.allocate(1);
+    let waiter = thread::spawn(move || {
+        let handle = arena.allocate_blocking(1);
+        drop(held);
+        handle.as_slice().len()
+    });
+
+    thread::sleep(Duration::from_millis(50));
+    drop(dedicated);
+    assert_eq!(waiter.join().unwrap(), 1);
+}
@@ -0,0 +1,43 @@
+use ring_arena::{RawRingArena, RingArena};
+use std::{alloc::Layout, num::NonZero};
+
+#[test]
+fn zero_size_layouts_are_aligned() {
+    let mut arena = RawRingArena::new(NonZero::new(64).unwrap());
+    for align in [1, 8, 64, 4096] {
+        let handle = arena.allocate(Layout::from_size_align(0, align).unwrap());
+        assert!(handle.as_slice().is_empty());
+        assert_eq!(handle.as_slice().as_ptr().addr() % align, 0);
+    }
+}
+
+#[test]
+fn zero_sized_slices_are_aligned_and_not_boxed() {
+    #[repr(align(32))]
+    struct Marker;
+
+    let mut raw = RawRingArena::new(NonZero::new(64).unwrap());
+    let handle = raw.alloc_slice::<Marker>(5);
+    assert!(!handle.is_boxed());
+    assert_eq!(handle.as_slice().len(), 5);
+    assert_eq!(handle.as_slice().as_ptr().addr() % 32, 0);
+
+    let mut typed = RingArena::<Marker>::new(NonZero::new(8).unwrap());
+    assert!(!typed.allocate(5).is_boxed());
+}
+
+#[test]
+fn zero_size_allocations_dont_keep_a_chunk_in_use() {
+    let mut arena = RawRingArena::new(NonZero::new(64).unwrap());
+    arena.set_max_chunks(NonZero::new(1));
+
+    let _unit = arena.alloc::<()>();
+    let _empty = arena.allocate(Layout::from_size_align(0, 64).unwrap());
+    let _units = arena.alloc_slice::<()>(8);
+    assert_eq!(arena.stats().busy_chunks, 0);
+
+    // the only chunk is free once full, so it's reused rather than exhausting the arena
+    drop(arena.allocate(Layout::array::<u8>(64).unwrap()));
+    assert!(arena.try_allocate(Layout::new::<u64>()).is_ok());
+    assert_eq!(arena.stats().chunks, 1);
+}
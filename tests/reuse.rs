@@ -0,0 +1,52 @@
+use ring_arena::RingArena;
+use std::num::NonZero;
+
+#[test]
+fn long_lived_handle_does_not_grow_the_ring() {
+    let mut arena = RingArena::<u64>::new(NonZero::new(4).unwrap());
+    let long_lived = arena.allocate(4);
+
+    for _ in 0..100 {
+        let handle = arena.allocate(4);
+        drop(handle);
+    }
+
+    // the busy chunk is skipped, and the other one is reused every time
+    let stats = arena.stats();
+    assert_eq!(stats.chunks, 2);
+    assert_eq!(stats.chunks_created, 1);
+    drop(long_lived);
+}
+
+#[test]
+fn free_chunks_behind_busy_ones_are_reused() {
+    let mut arena = RingArena::<u64>::new(NonZero::new(1).unwrap());
+    let mut handles: Vec<_> = (0..4).map(|_| arena.allocate(1)).collect();
+    assert_eq!(arena.stats().chunks, 4);
+
+    // free a chunk between busy ones
+    let freed = handles.remove(1);
+    let freed_ptr = freed.as_slice().as_ptr();
+    drop(freed);
+
+    let handle = arena.allocate(1);
+    assert_eq!(handle.as_slice().as_ptr(), freed_ptr);
+    assert_eq!(arena.stats().chunks, 4);
+}
+
+#[test]
+fn chunks_are_reused_in_release_order() {
+    let mut arena = RingArena::<u64>::new(NonZero::new(1).unwrap());
+    let mut handles: Vec<_> = (0..3).map(|_| arena.allocate(1)).collect();
+    let ptrs: Vec<_> = handles.iter().map(|h| h.as_slice().as_ptr()).collect();
+
+    // release the middle chunk before the first one, against the ring order
+    drop(handles.remove(1));
+    drop(handles.remove(0));
+
+    let first = arena.allocate(1);
+    let second = arena.allocate(1);
+    assert_eq!(first.as_slice().as_ptr(), ptrs[1]);
+    assert_eq!(second.as_slice().as_ptr(), ptrs[0]);
+    assert_eq!(arena.stats().chunks, 3);
+}
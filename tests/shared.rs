@@ -0,0 +1,87 @@
+mod common;
+
+use common::Tracked;
+use ring_arena::{AllocError, RingArena};
+use std::{cell::Cell, num::NonZero, rc::Rc};
+
+/// Arena with a single chunk of 4 elements, so the next allocation after filling it only succeeds
+/// once the chunk is free again.
+fn single_chunk<T>() -> RingArena<T> {
+    let mut arena = RingArena::new(NonZero::new(4).unwrap());
+    arena.set_max_chunks(NonZero::new(1));
+    arena
+}
+
+#[test]
+fn clones_share_the_chunk_without_dropping() {
+    let mut arena = single_chunk::<u32>();
+    let shared = arena.allocate_with(4, |i| i as u32).into_shared();
+    let clone = shared.clone();
+    assert_eq!(*clone, [0, 1, 2, 3]);
+    assert_eq!(clone.as_ptr(), shared.as_ptr());
+
+    drop(shared);
+    assert_eq!(arena.stats().busy_chunks, 1);
+    assert_eq!(arena.try_allocate(1).err(), Some(AllocError::Exhausted));
+
+    drop(clone);
+    assert_eq!(arena.stats().busy_chunks, 0);
+    assert!(arena.try_allocate(1).is_ok());
+}
+
+#[test]
+fn elements_are_dropped_once_with_the_last_clone() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = single_chunk();
+    let shared = arena
+        .allocate_with(4, |_| Tracked::new(&drops))
+        .into_shared();
+    let mut clones = vec![shared.clone(); 3];
+
+    drop(shared);
+    clones.truncate(1);
+    assert_eq!(drops.get(), 0);
+    assert_eq!(arena.try_allocate(1).err(), Some(AllocError::Exhausted));
+
+    drop(clones);
+    assert_eq!(drops.get(), 4);
+    assert!(arena.try_allocate(1).is_ok());
+}
+
+#[test]
+fn boxed_handles_can_be_shared() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = RingArena::new(NonZero::new(4).unwrap());
+    let handle = arena.allocate_with(8, |_| Tracked::new(&drops));
+    assert!(handle.is_boxed());
+
+    let shared = handle.into_shared();
+    let clone = shared.clone();
+    assert_eq!(clone.len(), 8);
+
+    drop(shared);
+    assert_eq!(drops.get(), 0);
+    drop(clone);
+    assert_eq!(drops.get(), 8);
+}
+
+#[test]
+fn slices_keep_the_chunk_until_every_piece_is_gone() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = single_chunk();
+    let shared = arena
+        .allocate_with(4, |_| Tracked::new(&drops))
+        .into_shared();
+    let header = shared.slice(..1);
+    let payload = shared.slice(1..);
+    assert_eq!(payload.as_ptr(), shared[1..].as_ptr());
+    assert_eq!(payload.slice(1..2).len(), 1);
+
+    drop((shared, header));
+    assert_eq!(drops.get(), 0);
+    assert_eq!(arena.try_allocate(1).err(), Some(AllocError::Exhausted));
+
+    drop(payload);
+    assert_eq!(drops.get(), 4);
+    assert!(arena.try_allocate(1).is_ok());
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::CountingSource;
+use ring_arena::{AllocError, ConcurrentRingArena, SharedChunkPool};
+use std::num::NonZero;
+
+#[test]
+fn pool_chunks_come_from_its_source() {
+    let source = CountingSource::with_limit(2);
+    let pool = SharedChunkPool::<u32>::with_source(NonZero::new(4).unwrap(), source.clone());
+    let mut arena = pool.arena().unwrap();
+
+    let first = arena.allocate(4);
+    let second = arena.allocate(4);
+    assert_eq!(source.allocated(), 2);
+
+    // the source is out of chunks, and the pool has no limit of its own
+    assert_eq!(arena.try_allocate(4).err(), Some(AllocError::Exhausted));
+
+    drop((first, second, arena));
+    pool.trim();
+    assert_eq!(source.freed(), 2);
+}
+
+#[test]
+fn concurrent_chunks_come_from_its_source() {
+    let source = CountingSource::with_limit(2);
+    let arena =
+        ConcurrentRingArena::<u32>::with_source(NonZero::new(4).unwrap(), source.clone()).unwrap();
+
+    let first = arena.allocate(4);
+    let second = arena.allocate(4);
+    assert_eq!(source.allocated(), 2);
+    assert_eq!(arena.try_allocate(4).err(), Some(AllocError::Exhausted));
+
+    drop((first, second, arena));
+    assert_eq!(source.freed(), 2);
+}
+
+#[test]
+fn concurrent_arena_fails_without_a_first_chunk() {
+    let source = CountingSource::with_limit(0);
+    let arena = ConcurrentRingArena::<u32>::with_source(NonZero::new(4).unwrap(), source);
+    assert_eq!(arena.err(), Some(AllocError::Exhausted));
+}
@@ -0,0 +1,64 @@
+use ring_arena::RingArena;
+use std::num::NonZero;
+
+/// Allocates and drops a whole chunk, so that the next allocation rotates.
+fn rotate(arena: &mut RingArena<u64>) {
+    drop(arena.allocate(1));
+}
+
+#[test]
+fn trim_after_shrinks_the_ring_back_after_a_burst() {
+    let mut arena = RingArena::<u64>::new(NonZero::new(1).unwrap());
+    arena.set_trim_after(NonZero::new(20));
+
+    drop((0..10).map(|_| arena.allocate(1)).collect::<Vec<_>>());
+    assert_eq!(arena.stats().chunks, 10);
+
+    // a single chunk is enough from now on, so the others stay idle
+    for _ in 0..20 {
+        rotate(&mut arena);
+    }
+    assert!(arena.stats().chunks > 1);
+
+    for _ in 0..10 {
+        rotate(&mut arena);
+    }
+    assert_eq!(arena.stats().chunks, 1);
+}
+
+#[test]
+fn trim_after_counts_idle_rotations_from_the_last_release() {
+    let mut arena = RingArena::<u64>::new(NonZero::new(1).unwrap());
+    arena.set_trim_after(NonZero::new(2));
+
+    let long_lived = arena.allocate(1);
+    for _ in 0..10 {
+        rotate(&mut arena);
+    }
+    assert_eq!(arena.stats().chunks, 2);
+
+    // the chunk was busy all along, so it's not trimmed right away
+    drop(long_lived);
+    rotate(&mut arena);
+    assert_eq!(arena.stats().chunks, 2);
+    rotate(&mut arena);
+    assert_eq!(arena.stats().chunks, 2);
+
+    rotate(&mut arena);
+    assert_eq!(arena.stats().chunks, 1);
+}
+
+#[test]
+fn trim_keeps_the_front_and_busy_chunks() {
+    let mut arena = RingArena::<u64>::new(NonZero::new(1).unwrap());
+    let handles: Vec<_> = (0..4).map(|_| arena.allocate(1)).collect();
+    let busy = arena.allocate(1);
+    drop(handles);
+    assert_eq!(arena.stats().chunks, 5);
+
+    arena.trim();
+    let stats = arena.stats();
+    assert_eq!(stats.chunks, 1);
+    assert_eq!(stats.busy_chunks, 1);
+    drop(busy);
+}
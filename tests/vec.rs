@@ -0,0 +1,71 @@
+mod common;
+
+use common::Tracked;
+use ring_arena::RingArena;
+use std::{cell::Cell, num::NonZero, rc::Rc};
+
+#[test]
+fn grows_in_place_while_last() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(16).unwrap());
+    let mut vec = arena.vec();
+    vec.push(0);
+    let start = vec.as_ptr();
+
+    vec.extend(1..10);
+    assert_eq!(vec.as_ptr(), start);
+    assert_eq!(*vec, *(0..10).collect::<Vec<_>>());
+
+    let handle = vec.finish();
+    assert_eq!(*handle, *(0..10).collect::<Vec<_>>());
+    assert_eq!(arena.stats().front_offset, 10);
+}
+
+#[test]
+fn moves_to_a_new_chunk_once_the_front_is_full() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(8).unwrap());
+    let _busy = arena.allocate(4);
+
+    let mut vec = arena.vec();
+    vec.extend(0..4);
+    let start = vec.as_ptr();
+
+    // no room is left after the vector, so its elements move to a fresh chunk
+    vec.push(4);
+    assert_ne!(vec.as_ptr(), start);
+    assert_eq!(*vec, [0, 1, 2, 3, 4]);
+
+    let handle = vec.finish();
+    assert_eq!(*handle, [0, 1, 2, 3, 4]);
+    let stats = arena.stats();
+    assert_eq!(stats.chunks, 2);
+    assert_eq!(stats.front_offset, 5);
+}
+
+#[test]
+fn moved_elements_are_dropped_once() {
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = RingArena::<Tracked>::new(NonZero::new(4).unwrap());
+    let _busy = arena.allocate(2);
+
+    let mut vec = arena.vec();
+    vec.extend((0..6).map(|_| Tracked::new(&drops)));
+    assert_eq!(drops.get(), 0);
+
+    vec.truncate(4);
+    assert_eq!(drops.get(), 2);
+
+    drop(vec);
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn dropping_an_unfinished_vector_gives_back_its_space() {
+    let mut arena = RingArena::<u32>::new(NonZero::new(16).unwrap());
+    let _first = arena.allocate(2);
+
+    let mut vec = arena.vec();
+    vec.extend(0..5);
+    drop(vec);
+
+    assert_eq!(arena.stats().front_offset, 2);
+}